@@ -1,6 +1,6 @@
 //! Example demonstrating environment variable configuration layer usage.
 
-use spicex::{ConfigLayer, ConfigValue, EnvConfigLayer};
+use spicex::{ConfigLayer, EnvConfigLayer};
 use std::env;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -9,7 +9,7 @@
 //! Run with: cargo run --example migration_guide --features cli
 
 use serde::Deserialize;
-use spicex::{ConfigError, ConfigValue, Spice};
+use spicex::{ConfigValue, Spice};
 use std::collections::HashMap;
 
 #[derive(Deserialize, Debug, PartialEq)]
@@ -222,6 +222,7 @@ impl AppConfig {
     }
 
     /// Gets service configuration by name
+    #[allow(dead_code)]
     pub fn get_service(&self, name: &str) -> Option<&ServiceConfig> {
         self.services.get(name)
     }
@@ -121,7 +121,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         spice_instance.unmarshal_key_with_validation("database", |config: &DatabaseConfig| {
             config
                 .validate()
-                .map_err(|e| spicex::ConfigError::invalid_value(e))
+                .map_err(spicex::ConfigError::invalid_value)
         })?;
     println!("✓ Database configuration validated successfully");
     println!("  Validated host: {}", validated_config.host);
@@ -141,7 +141,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         |config: &DatabaseConfig| {
             config
                 .validate()
-                .map_err(|e| spicex::ConfigError::invalid_value(e))
+                .map_err(spicex::ConfigError::invalid_value)
         },
     ) {
         Ok(_) => println!("❌ Expected validation error"),
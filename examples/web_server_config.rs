@@ -8,7 +8,6 @@ use spicex::{ConfigValue, EnvConfigLayer, Spice};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 struct ServerConfig {
@@ -22,7 +21,7 @@ struct ServerConfig {
     timeouts: TimeoutConfig,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 struct SslConfig {
     #[serde(default)]
     enabled: bool,
@@ -30,16 +29,6 @@ struct SslConfig {
     key_file: Option<String>,
 }
 
-impl Default for SslConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            cert_file: None,
-            key_file: None,
-        }
-    }
-}
-
 #[derive(Debug, Deserialize, Serialize, Clone)]
 struct TimeoutConfig {
     #[serde(default = "default_read_timeout")]
@@ -325,7 +314,7 @@ fn load_config_files(spice_instance: &mut Spice) -> Result<(), Box<dyn std::erro
     }
 
     // Try to load environment-specific configuration
-    spice_instance.set_config_name(&format!("config.{}", env));
+    spice_instance.set_config_name(format!("config.{}", env));
     match spice_instance.read_in_config() {
         Ok(()) => println!("   ✓ Loaded {}-specific configuration", env),
         Err(_) => println!("   ⚠ No {}-specific configuration found", env),
@@ -449,7 +438,9 @@ fn display_configuration(config: &AppConfig) {
     }
 }
 
-fn demonstrate_individual_access(spice_instance: &mut Spice) -> Result<(), Box<dyn std::error::Error>> {
+fn demonstrate_individual_access(
+    spice_instance: &mut Spice,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔍 Demonstrating individual configuration access:");
     println!("================================================");
 
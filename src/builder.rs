@@ -0,0 +1,227 @@
+//! Fluent construction of `Spice` instances.
+
+use crate::config::Spice;
+use crate::error::ConfigResult;
+use crate::value::ConfigValue;
+use std::path::PathBuf;
+
+/// Builder that configures a `Spice` instance and loads its configuration in one step.
+///
+/// Each method records a setting; `build` applies them through the same `Spice` methods
+/// you would call by hand, so the result is identical to the equivalent sequence of
+/// `set_config_name`, `add_config_path`, `set_env_prefix`, `set_automatic_env`,
+/// `set_default`, and `read_in_config` or `set_config_file` calls.
+///
+/// # Example
+/// ```
+/// use spicex::{ConfigValue, SpiceBuilder};
+///
+/// let mut spice = SpiceBuilder::new()
+///     .env_prefix("MYAPP")
+///     .automatic_env(true)
+///     .default("server.port", ConfigValue::from(8080i64))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(spice.env_prefix(), Some("MYAPP"));
+/// assert_eq!(spice.get_int("server.port").unwrap(), Some(8080));
+/// ```
+#[derive(Debug)]
+pub struct SpiceBuilder {
+    config_name: Option<String>,
+    config_paths: Vec<PathBuf>,
+    config_file: Option<PathBuf>,
+    env_prefix: Option<String>,
+    automatic_env: bool,
+    defaults: Vec<(String, ConfigValue)>,
+}
+
+impl SpiceBuilder {
+    /// Creates a builder with no settings.
+    pub fn new() -> Self {
+        Self {
+            config_name: None,
+            config_paths: Vec::new(),
+            config_file: None,
+            env_prefix: None,
+            automatic_env: false,
+            defaults: Vec::new(),
+        }
+    }
+
+    /// Sets the configuration file name (without extension) to search for.
+    ///
+    /// # Arguments
+    /// * `name` - The configuration file name
+    pub fn config_name(mut self, name: impl Into<String>) -> Self {
+        self.config_name = Some(name.into());
+        self
+    }
+
+    /// Adds a path to search for configuration files. May be called more than once.
+    ///
+    /// # Arguments
+    /// * `path` - The path to add to the search list
+    pub fn config_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_paths.push(path.into());
+        self
+    }
+
+    /// Sets an explicit configuration file to load instead of searching.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the configuration file
+    pub fn config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_file = Some(path.into());
+        self
+    }
+
+    /// Sets the environment variable prefix.
+    ///
+    /// # Arguments
+    /// * `prefix` - The prefix to use for environment variables
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets whether to automatically bind environment variables.
+    ///
+    /// # Arguments
+    /// * `automatic` - Whether to enable automatic environment variable binding
+    pub fn automatic_env(mut self, automatic: bool) -> Self {
+        self.automatic_env = automatic;
+        self
+    }
+
+    /// Adds a default value for a configuration key.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to set a default for
+    /// * `value` - The default configuration value
+    pub fn default(mut self, key: impl Into<String>, value: ConfigValue) -> Self {
+        self.defaults.push((key.into(), value));
+        self
+    }
+
+    /// Creates the `Spice` instance and loads its configuration file.
+    ///
+    /// Settings and defaults are applied first. Then the explicit file set with
+    /// `config_file` is loaded, or, if only `config_name` was given, the search paths
+    /// are searched with `read_in_config`. Without either, no file is loaded.
+    ///
+    /// # Returns
+    /// * `ConfigResult<Spice>` - The configured instance, or an error
+    ///
+    /// # Errors
+    /// * `ConfigError::KeyNotFound` - If `config_name` was given and no file is found
+    /// * `ConfigError::Io` - If the configuration file cannot be read
+    /// * `ConfigError::Parse` - If the configuration file cannot be parsed
+    pub fn build(self) -> ConfigResult<Spice> {
+        let mut spice = Spice::new();
+
+        if let Some(name) = &self.config_name {
+            spice.set_config_name(name.as_str());
+        }
+        for path in self.config_paths {
+            spice.add_config_path(path);
+        }
+        if let Some(prefix) = self.env_prefix {
+            spice.set_env_prefix(prefix);
+        }
+        spice.set_automatic_env(self.automatic_env);
+        for (key, value) in self.defaults {
+            spice.set_default(&key, value)?;
+        }
+
+        if let Some(config_file) = self.config_file {
+            spice.set_config_file(config_file)?;
+        } else if self.config_name.is_some() {
+            spice.read_in_config()?;
+        }
+
+        Ok(spice)
+    }
+}
+
+impl Default for SpiceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Spice {
+    /// Returns a builder for fluent construction of a Spice instance.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use spicex::Spice;
+    ///
+    /// let spice = Spice::builder()
+    ///     .config_name("config")
+    ///     .config_path("./configs")
+    ///     .config_path("/etc/myapp")
+    ///     .env_prefix("MYAPP")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> SpiceBuilder {
+        SpiceBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_build_discovers_config_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("app.json"),
+            r#"{"server": {"host": "file.internal"}}"#,
+        )
+        .unwrap();
+
+        let mut spice = Spice::builder()
+            .config_name("app")
+            .config_path(temp_dir.path().join("missing"))
+            .config_path(temp_dir.path())
+            .default("server.host", ConfigValue::from("localhost"))
+            .default("server.port", ConfigValue::from(8080i64))
+            .build()
+            .unwrap();
+
+        assert_eq!(spice.config_name(), "app");
+        assert_eq!(spice.config_paths().len(), 2);
+        assert_eq!(
+            spice.get_string("server.host").unwrap(),
+            Some("file.internal".to_string())
+        );
+        assert_eq!(spice.get_int("server.port").unwrap(), Some(8080));
+    }
+
+    #[test]
+    fn test_build_loads_explicit_file_and_reports_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("explicit.yaml");
+        fs::write(&config_file, "debug: true\n").unwrap();
+
+        let mut spice = SpiceBuilder::new()
+            .config_name("ignored")
+            .config_file(&config_file)
+            .build()
+            .unwrap();
+        assert_eq!(spice.get_bool("debug").unwrap(), Some(true));
+
+        let missing = SpiceBuilder::new()
+            .config_name("absent")
+            .config_path(temp_dir.path())
+            .build();
+        assert!(missing.is_err());
+
+        assert!(SpiceBuilder::new().build().is_ok());
+    }
+}
@@ -3,8 +3,9 @@
 use crate::error::{ConfigError, ConfigResult};
 use crate::layer::{ConfigLayer, LayerPriority};
 use crate::value::ConfigValue;
-use clap::ArgMatches;
-use std::collections::HashMap;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Command};
+use std::collections::{HashMap, HashSet};
 
 /// Configuration layer that provides values from command line flags.
 ///
@@ -14,7 +15,7 @@ use std::collections::HashMap;
 ///
 /// # Example
 /// ```
-/// use spice::cli::FlagConfigLayer;
+/// use spicex::cli::FlagConfigLayer;
 /// use clap::{Arg, Command};
 ///
 /// let app = Command::new("myapp")
@@ -32,6 +33,7 @@ use std::collections::HashMap;
 ///
 /// // The flag values are now available as configuration
 /// ```
+#[derive(Clone)]
 pub struct FlagConfigLayer {
     /// Parsed command line arguments
     matches: ArgMatches,
@@ -39,6 +41,37 @@ pub struct FlagConfigLayer {
     cached_values: HashMap<String, ConfigValue>,
     /// Flag to key mappings for custom key names
     flag_mappings: HashMap<String, String>,
+    /// Paired `--feature`/`--no-feature` flags, keyed by configuration key
+    negatable_flags: HashMap<String, (String, String)>,
+    /// Which of the values clap reports are exposed, by where they came from
+    exposed: ExposedValues,
+    /// Arguments declared to take several values, which are read as arrays even when
+    /// given once
+    multi_value_args: HashSet<String>,
+}
+
+/// The values a flag layer exposes, by the `ValueSource` clap reports for them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExposedValues {
+    /// Only values typed on the command line
+    CommandLine,
+    /// Values from the command line or an argument's environment variable
+    CommandLineAndEnv,
+    /// Only values clap took from an argument's environment variable
+    Env,
+    /// Every value, including argument defaults
+    All,
+}
+
+impl ExposedValues {
+    fn includes(self, source: Option<ValueSource>) -> bool {
+        match self {
+            ExposedValues::CommandLine => source == Some(ValueSource::CommandLine),
+            ExposedValues::CommandLineAndEnv => source != Some(ValueSource::DefaultValue),
+            ExposedValues::Env => source == Some(ValueSource::EnvVariable),
+            ExposedValues::All => true,
+        }
+    }
 }
 
 impl FlagConfigLayer {
@@ -52,7 +85,7 @@ impl FlagConfigLayer {
     ///
     /// # Example
     /// ```
-    /// use spice::cli::FlagConfigLayer;
+    /// use spicex::cli::FlagConfigLayer;
     /// use clap::{Arg, Command};
     ///
     /// let app = Command::new("myapp")
@@ -71,6 +104,59 @@ impl FlagConfigLayer {
             matches,
             cached_values: HashMap::new(),
             flag_mappings: HashMap::new(),
+            negatable_flags: HashMap::new(),
+            exposed: ExposedValues::CommandLine,
+            multi_value_args: HashSet::new(),
+        };
+        layer.cache_flag_values();
+        layer
+    }
+
+    /// Creates a flag configuration layer that knows which arguments take several values.
+    /// Arguments declared on `command` with `ArgAction::Append` or a `num_args` maximum
+    /// above one always read as a `ConfigValue::Array`, even when given once, so a single
+    /// value such as `a,b` is not split apart later by `get_string_slice`. Layers created
+    /// with `new` cannot tell such an argument given once from a single-valued one.
+    ///
+    /// # Arguments
+    /// * `matches` - The parsed ArgMatches from clap
+    /// * `command` - The clap command the matches were parsed with
+    ///
+    /// # Returns
+    /// * `FlagConfigLayer` - A new flag configuration layer
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::cli::FlagConfigLayer;
+    /// use spicex::{ConfigLayer, ConfigValue};
+    /// use clap::{Arg, ArgAction, Command};
+    ///
+    /// let app = Command::new("myapp").arg(Arg::new("header").long("header").action(ArgAction::Append));
+    /// let matches = app.clone().try_get_matches_from(vec!["myapp", "--header", "a,b"]).unwrap();
+    ///
+    /// let flag_layer = FlagConfigLayer::with_command(matches, &app);
+    /// assert_eq!(
+    ///     flag_layer.get("header").unwrap(),
+    ///     Some(ConfigValue::Array(vec![ConfigValue::from("a,b")]))
+    /// );
+    /// ```
+    pub fn with_command(matches: ArgMatches, command: &Command) -> Self {
+        let mut layer = Self {
+            matches,
+            cached_values: HashMap::new(),
+            flag_mappings: HashMap::new(),
+            negatable_flags: HashMap::new(),
+            exposed: ExposedValues::CommandLine,
+            multi_value_args: command
+                .get_arguments()
+                .filter(|arg| {
+                    matches!(arg.get_action(), clap::ArgAction::Append)
+                        || arg
+                            .get_num_args()
+                            .is_some_and(|range| range.max_values() > 1)
+                })
+                .map(|arg| arg.get_id().as_str().to_string())
+                .collect(),
         };
         layer.cache_flag_values();
         layer
@@ -87,7 +173,7 @@ impl FlagConfigLayer {
     ///
     /// # Example
     /// ```
-    /// use spice::cli::FlagConfigLayer;
+    /// use spicex::cli::FlagConfigLayer;
     /// use clap::{Arg, Command};
     /// use std::collections::HashMap;
     ///
@@ -111,6 +197,9 @@ impl FlagConfigLayer {
             matches,
             cached_values: HashMap::new(),
             flag_mappings: mappings,
+            negatable_flags: HashMap::new(),
+            exposed: ExposedValues::CommandLine,
+            multi_value_args: HashSet::new(),
         };
         layer.cache_flag_values();
         layer
@@ -124,7 +213,7 @@ impl FlagConfigLayer {
     ///
     /// # Example
     /// ```
-    /// use spice::cli::FlagConfigLayer;
+    /// use spicex::cli::FlagConfigLayer;
     /// use clap::{Arg, Command};
     ///
     /// let app = Command::new("myapp")
@@ -164,6 +253,203 @@ impl FlagConfigLayer {
         result
     }
 
+    /// Binds a pair of boolean flags such as `--cache`/`--no-cache` to one configuration key.
+    /// The positive flag sets the key to `true` and the negative flag sets it to `false`.
+    /// When both are given, the one specified last on the command line wins; when neither
+    /// is given, the key is left unset so lower precedence sources apply.
+    ///
+    /// # Arguments
+    /// * `positive` - The name of the flag that enables the setting (as defined in clap)
+    /// * `negative` - The name of the flag that disables the setting (as defined in clap)
+    /// * `config_key` - The configuration key to bind both flags to
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::cli::FlagConfigLayer;
+    /// use spicex::{ConfigLayer, ConfigValue};
+    /// use clap::{Arg, ArgAction, Command};
+    ///
+    /// let app = Command::new("myapp")
+    ///     .arg(Arg::new("cache").long("cache").action(ArgAction::SetTrue))
+    ///     .arg(Arg::new("no-cache").long("no-cache").action(ArgAction::SetTrue));
+    ///
+    /// let matches = app
+    ///     .try_get_matches_from(vec!["myapp", "--cache", "--no-cache"])
+    ///     .unwrap();
+    /// let mut flag_layer = FlagConfigLayer::new(matches);
+    /// flag_layer.bind_negatable_flag("cache", "no-cache", "cache");
+    ///
+    /// assert_eq!(flag_layer.get("cache").unwrap(), Some(ConfigValue::Boolean(false)));
+    /// ```
+    pub fn bind_negatable_flag(
+        &mut self,
+        positive: impl Into<String>,
+        negative: impl Into<String>,
+        config_key: impl Into<String>,
+    ) {
+        self.negatable_flags
+            .insert(config_key.into(), (positive.into(), negative.into()));
+        // Re-cache values to apply the new binding
+        self.cache_flag_values();
+    }
+
+    /// Sets whether only flags given on the command line are exposed.
+    /// This is enabled by default, so an argument clap filled in from its default value or
+    /// its environment variable does not override lower precedence sources such as
+    /// configuration files. Disabling it exposes every value clap reports.
+    ///
+    /// # Arguments
+    /// * `command_line_only` - Whether to ignore values that were not typed by the user
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::cli::FlagConfigLayer;
+    /// use spicex::{ConfigLayer, ConfigValue};
+    /// use clap::{Arg, Command};
+    ///
+    /// let app = Command::new("myapp").arg(Arg::new("port").long("port").default_value("8080"));
+    /// let matches = app.try_get_matches_from(vec!["myapp"]).unwrap();
+    /// let mut flag_layer = FlagConfigLayer::new(matches);
+    /// assert_eq!(flag_layer.get("port").unwrap(), None);
+    ///
+    /// flag_layer.set_command_line_only(false);
+    /// assert_eq!(flag_layer.get("port").unwrap(), Some(ConfigValue::Integer(8080)));
+    /// ```
+    pub fn set_command_line_only(&mut self, command_line_only: bool) {
+        self.exposed = if command_line_only {
+            ExposedValues::CommandLine
+        } else {
+            ExposedValues::All
+        };
+        // Re-cache values to apply the change
+        self.cache_flag_values();
+    }
+
+    /// Sets whether values clap filled in from an argument's default are included.
+    /// When disabled, values given on the command line or through an argument's
+    /// environment variable are reported.
+    ///
+    /// # Arguments
+    /// * `include` - Whether to include default values
+    #[deprecated(
+        since = "0.1.3",
+        note = "use `set_command_line_only`, and `env_layer` for values from environment variables"
+    )]
+    pub fn set_include_default_values(&mut self, include: bool) {
+        self.exposed = if include {
+            ExposedValues::All
+        } else {
+            ExposedValues::CommandLineAndEnv
+        };
+        // Re-cache values to apply the change
+        self.cache_flag_values();
+    }
+
+    /// Returns a layer with the values clap took from arguments' environment variables
+    /// (`Arg::env`), at `LayerPriority::Environment`, or None if there are none. A layer
+    /// that is `command_line_only` leaves these values out, so binding this layer as well
+    /// keeps them above configuration files but below typed flags.
+    ///
+    /// # Returns
+    /// * `Option<FlagConfigLayer>` - The environment-sourced values, if any
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::cli::FlagConfigLayer;
+    /// use spicex::{ConfigLayer, ConfigValue, LayerPriority};
+    /// use spicex::env_layer::EnvGuard;
+    /// use clap::{Arg, Command};
+    ///
+    /// let mut guard = EnvGuard::new();
+    /// guard.set("DOC_FLAG_ENV_PORT", "9000");
+    /// let app = Command::new("myapp").arg(Arg::new("port").long("port").env("DOC_FLAG_ENV_PORT"));
+    /// let matches = app.try_get_matches_from(vec!["myapp"]).unwrap();
+    ///
+    /// let flag_layer = FlagConfigLayer::new(matches);
+    /// assert_eq!(flag_layer.get("port").unwrap(), None);
+    ///
+    /// let env_layer = flag_layer.env_layer().unwrap();
+    /// assert_eq!(env_layer.priority(), LayerPriority::Environment);
+    /// assert_eq!(env_layer.get("port").unwrap(), Some(ConfigValue::Integer(9000)));
+    /// ```
+    pub fn env_layer(&self) -> Option<FlagConfigLayer> {
+        let mut layer = self.clone();
+        layer.exposed = ExposedValues::Env;
+        layer.cache_flag_values();
+        if layer.cached_values.is_empty() {
+            None
+        } else {
+            Some(layer)
+        }
+    }
+
+    /// Collects the default values of the arguments defined on `command` that were not
+    /// given on the command line or through an environment variable, keyed by
+    /// configuration key. Values clap took from an argument's environment variable are
+    /// omitted; see `env_layer`. Flag mappings apply to the keys as they do to flag values.
+    ///
+    /// # Arguments
+    /// * `command` - The clap command the matches were parsed with
+    ///
+    /// # Returns
+    /// * `HashMap<String, ConfigValue>` - The default values of the unset arguments
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::cli::FlagConfigLayer;
+    /// use spicex::ConfigValue;
+    /// use clap::{Arg, Command};
+    ///
+    /// let app = Command::new("myapp")
+    ///     .arg(Arg::new("host").long("host").default_value("localhost"))
+    ///     .arg(Arg::new("port").long("port").default_value("8080"));
+    /// let matches = app
+    ///     .clone()
+    ///     .try_get_matches_from(vec!["myapp", "--port", "9000"])
+    ///     .unwrap();
+    ///
+    /// let defaults = FlagConfigLayer::new(matches).default_values(&app);
+    /// assert_eq!(defaults.get("host"), Some(&ConfigValue::from("localhost")));
+    /// assert_eq!(defaults.get("port"), None);
+    /// ```
+    pub fn default_values(&self, command: &Command) -> HashMap<String, ConfigValue> {
+        let mut defaults = HashMap::new();
+
+        for arg in command.get_arguments() {
+            let arg_name = arg.get_id().as_str();
+            let default_strings = arg.get_default_values();
+            if default_strings.is_empty() || self.is_negatable_flag(arg_name) {
+                continue;
+            }
+
+            let value_source = match self.matches.try_contains_id(arg_name) {
+                Ok(true) => self.matches.value_source(arg_name),
+                _ => None,
+            };
+            let value = match value_source {
+                Some(ValueSource::DefaultValue) => self.convert_arg_to_config_value(arg_name),
+                Some(_) => continue,
+                None => {
+                    let mut values: Vec<ConfigValue> = default_strings
+                        .iter()
+                        .map(|value| self.parse_string_value(&value.to_string_lossy()))
+                        .collect();
+                    if values.len() == 1 && !self.multi_value_args.contains(arg_name) {
+                        values.pop()
+                    } else {
+                        Some(ConfigValue::Array(values))
+                    }
+                }
+            };
+
+            if let Some(value) = value {
+                defaults.insert(self.config_key(arg_name), value);
+            }
+        }
+
+        defaults
+    }
+
     /// Gets all current flag mappings.
     ///
     /// # Returns
@@ -181,18 +467,70 @@ impl FlagConfigLayer {
         for arg_id in self.matches.ids() {
             let arg_name = arg_id.as_str();
 
-            // Determine the configuration key (use mapping if available, otherwise use flag name)
-            let config_key = self
-                .flag_mappings
-                .get(arg_name)
-                .cloned()
-                .unwrap_or_else(|| self.normalize_flag_name(arg_name));
+            // Negatable flags are resolved together below
+            if self.is_negatable_flag(arg_name) {
+                continue;
+            }
+
+            if !self.exposed.includes(self.matches.value_source(arg_name)) {
+                continue;
+            }
+
+            let config_key = self.config_key(arg_name);
 
             // Convert the argument value to ConfigValue
             if let Some(config_value) = self.convert_arg_to_config_value(arg_name) {
                 self.cached_values.insert(config_key, config_value);
             }
         }
+
+        // Negatable flags are only ever read from the command line
+        if self.exposed == ExposedValues::Env {
+            return;
+        }
+        for (config_key, (positive, negative)) in &self.negatable_flags {
+            let positive_index = self.command_line_index(positive);
+            let negative_index = self.command_line_index(negative);
+
+            // Whichever flag appears last on the command line wins
+            let enabled = match (positive_index, negative_index) {
+                (Some(p), Some(n)) => p > n,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => continue,
+            };
+            self.cached_values
+                .insert(config_key.clone(), ConfigValue::Boolean(enabled));
+        }
+    }
+
+    /// Determines the configuration key for a flag, using its mapping if one is set and
+    /// the normalized flag name otherwise.
+    fn config_key(&self, flag_name: &str) -> String {
+        self.flag_mappings
+            .get(flag_name)
+            .cloned()
+            .unwrap_or_else(|| self.normalize_flag_name(flag_name))
+    }
+
+    /// Returns true if the flag is half of a negatable flag pair.
+    fn is_negatable_flag(&self, flag_name: &str) -> bool {
+        self.negatable_flags
+            .values()
+            .any(|(positive, negative)| positive == flag_name || negative == flag_name)
+    }
+
+    /// Returns the position of the last occurrence of a flag given on the command line.
+    /// Flags that only carry a default value are treated as absent.
+    fn command_line_index(&self, flag_name: &str) -> Option<usize> {
+        if !matches!(self.matches.try_contains_id(flag_name), Ok(true))
+            || self.matches.value_source(flag_name) != Some(ValueSource::CommandLine)
+        {
+            return None;
+        }
+        self.matches
+            .indices_of(flag_name)
+            .and_then(|indices| indices.max())
     }
 
     /// Normalizes a flag name to a configuration key format.
@@ -204,10 +542,13 @@ impl FlagConfigLayer {
     /// # Returns
     /// * `String` - The normalized configuration key
     fn normalize_flag_name(&self, flag_name: &str) -> String {
-        flag_name.replace('-', ".").replace('_', ".").to_lowercase()
+        flag_name.replace(['-', '_'], ".").to_lowercase()
     }
 
     /// Converts a clap argument to a ConfigValue.
+    /// Arguments that received several values, such as `--header a --header b` with
+    /// `ArgAction::Append`, become an array, as do arguments known to take several values;
+    /// otherwise a single value stays scalar.
     ///
     /// # Arguments
     /// * `arg_name` - The name of the argument to convert
@@ -220,38 +561,48 @@ impl FlagConfigLayer {
             return None;
         }
 
-        // Try different value types in order of specificity
-
-        // 1. Try as boolean flag (SetTrue/SetFalse actions)
-        if let Ok(Some(&flag_val)) = self.matches.try_get_one::<bool>(arg_name) {
-            return Some(ConfigValue::Boolean(flag_val));
-        }
-
-        // 2. Try as multiple string values first (Append action)
-        if let Ok(Some(values)) = self.matches.try_get_many::<String>(arg_name) {
-            let config_values: Vec<ConfigValue> =
-                values.map(|v| self.parse_string_value(v)).collect();
-
-            if config_values.len() == 1 {
-                return Some(config_values.into_iter().next().unwrap());
-            } else {
-                return Some(ConfigValue::Array(config_values));
-            }
+        let mut values = self.arg_values(arg_name)?;
+        match values.len() {
+            0 => None,
+            1 if !self.multi_value_args.contains(arg_name) => values.pop(),
+            _ => Some(ConfigValue::Array(values)),
         }
+    }
 
-        // 3. Try as single string value (Set actions)
-        if let Ok(Some(string_val)) = self.matches.try_get_one::<String>(arg_name) {
-            return Some(self.parse_string_value(string_val));
-        }
+    /// Collects every value of a clap argument, whichever value parser produced it.
+    /// Boolean flags (SetTrue/SetFalse actions) and counts (Count action) yield one value.
+    fn arg_values(&self, arg_name: &str) -> Option<Vec<ConfigValue>> {
+        self.values_of::<bool>(arg_name, |b| ConfigValue::Boolean(*b))
+            .or_else(|| self.values_of::<String>(arg_name, |s| self.parse_string_value(s)))
+            .or_else(|| self.values_of::<i64>(arg_name, |i| ConfigValue::Integer(*i)))
+            .or_else(|| self.values_of::<i32>(arg_name, |i| ConfigValue::from(*i)))
+            .or_else(|| self.values_of::<u64>(arg_name, |i| ConfigValue::Integer(*i as i64)))
+            .or_else(|| self.values_of::<usize>(arg_name, |i| ConfigValue::Integer(*i as i64)))
+            .or_else(|| self.values_of::<u32>(arg_name, |i| ConfigValue::from(*i)))
+            .or_else(|| self.values_of::<u16>(arg_name, |i| ConfigValue::Integer(i64::from(*i))))
+            .or_else(|| self.values_of::<u8>(arg_name, |i| ConfigValue::Integer(i64::from(*i))))
+            .or_else(|| self.values_of::<f64>(arg_name, |f| ConfigValue::Float(*f)))
+            .or_else(|| self.values_of::<f32>(arg_name, |f| ConfigValue::from(*f)))
+            .or_else(|| {
+                self.values_of::<std::path::PathBuf>(arg_name, |path| {
+                    ConfigValue::String(path.to_string_lossy().into_owned())
+                })
+            })
+    }
 
-        // 4. Check for count values (Count action)
-        let count = self.matches.get_count(arg_name);
-        if count > 0 {
-            return Some(ConfigValue::Integer(count as i64));
+    /// Returns the values of an argument if its value parser produced values of type `T`.
+    fn values_of<T>(
+        &self,
+        arg_name: &str,
+        convert: impl Fn(&T) -> ConfigValue,
+    ) -> Option<Vec<ConfigValue>>
+    where
+        T: std::any::Any + Clone + Send + Sync + 'static,
+    {
+        match self.matches.try_get_many::<T>(arg_name) {
+            Ok(Some(values)) => Some(values.map(convert).collect()),
+            _ => None,
         }
-
-
-        None
     }
 
     /// Parses a string value and attempts to convert it to the most appropriate ConfigValue type.
@@ -331,11 +682,17 @@ impl ConfigLayer for FlagConfigLayer {
     }
 
     fn source_name(&self) -> &str {
-        "command line flags"
+        match self.exposed {
+            ExposedValues::Env => "command line flag environment variables",
+            _ => "command line flags",
+        }
     }
 
     fn priority(&self) -> LayerPriority {
-        LayerPriority::Flags
+        match self.exposed {
+            ExposedValues::Env => LayerPriority::Environment,
+            _ => LayerPriority::Flags,
+        }
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -345,9 +702,14 @@ impl ConfigLayer for FlagConfigLayer {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn ConfigLayer> {
+        Box::new(self.clone())
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
     use clap::{Arg, Command};
@@ -571,6 +933,213 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_only_command_line_values_are_bound() {
+        let app = create_test_app().arg(Arg::new("timeout").long("timeout").default_value("30"));
+        let matches = app
+            .try_get_matches_from(vec!["test", "--host", "localhost"])
+            .unwrap();
+        let mut flag_layer = FlagConfigLayer::new(matches);
+
+        assert_eq!(
+            flag_layer.get("host").unwrap(),
+            Some(ConfigValue::from("localhost"))
+        );
+        assert_eq!(flag_layer.get("timeout").unwrap(), None);
+        assert_eq!(flag_layer.get("verbose").unwrap(), None);
+        assert_eq!(flag_layer.keys(), vec!["host".to_string()]);
+
+        flag_layer.set_command_line_only(false);
+        assert_eq!(
+            flag_layer.get("timeout").unwrap(),
+            Some(ConfigValue::Integer(30))
+        );
+        assert_eq!(
+            flag_layer.get("verbose").unwrap(),
+            Some(ConfigValue::Boolean(false))
+        );
+    }
+
+    #[test]
+    fn test_env_sourced_values_bind_at_environment_priority() {
+        use crate::env_layer::EnvGuard;
+
+        let mut guard = EnvGuard::new();
+        guard.set("SPICEX_CLI_TEST_TIMEOUT", "45");
+        guard.set("SPICEX_CLI_TEST_HOST", "env.internal");
+        let app = create_test_app()
+            .arg(
+                Arg::new("timeout")
+                    .long("timeout")
+                    .env("SPICEX_CLI_TEST_TIMEOUT"),
+            )
+            .arg(Arg::new("region").long("region").default_value("eu"))
+            .mut_arg("host", |arg| arg.env("SPICEX_CLI_TEST_HOST"));
+        let matches = app
+            .clone()
+            .try_get_matches_from(vec!["test", "--host", "cli.internal"])
+            .unwrap();
+
+        let flag_layer = FlagConfigLayer::new(matches.clone());
+        assert_eq!(flag_layer.get("timeout").unwrap(), None);
+        let env_layer = flag_layer.env_layer().unwrap();
+        assert_eq!(env_layer.priority(), LayerPriority::Environment);
+        assert_eq!(env_layer.keys(), vec!["timeout".to_string()]);
+
+        // The environment value beats a config file but not an explicit setting
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("config.json");
+        std::fs::write(&config_file, r#"{"timeout": 10, "region": "us"}"#).unwrap();
+        let mut spice = crate::Spice::new();
+        spice.set_config_file(&config_file).unwrap();
+        spice.bind_flags_with_defaults(matches, &app);
+        assert_eq!(spice.get_int("timeout").unwrap(), Some(45));
+        assert_eq!(
+            spice.get_string("host").unwrap(),
+            Some("cli.internal".to_string())
+        );
+        assert_eq!(spice.get_string("region").unwrap(), Some("us".to_string()));
+        spice.bind_flag("timeout", "http.timeout").unwrap();
+        assert_eq!(spice.get_int("http.timeout").unwrap(), Some(45));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_include_default_values_keeps_env_values() {
+        use crate::env_layer::EnvGuard;
+
+        let mut guard = EnvGuard::new();
+        guard.set("SPICEX_CLI_TEST_RETRIES", "3");
+        let app = create_test_app()
+            .arg(
+                Arg::new("retries")
+                    .long("retries")
+                    .env("SPICEX_CLI_TEST_RETRIES"),
+            )
+            .arg(Arg::new("timeout").long("timeout").default_value("30"));
+        let matches = app.try_get_matches_from(vec!["test"]).unwrap();
+        let mut flag_layer = FlagConfigLayer::new(matches);
+
+        flag_layer.set_include_default_values(false);
+        assert_eq!(
+            flag_layer.get("retries").unwrap(),
+            Some(ConfigValue::Integer(3))
+        );
+        assert_eq!(flag_layer.get("timeout").unwrap(), None);
+
+        flag_layer.set_include_default_values(true);
+        assert_eq!(
+            flag_layer.get("timeout").unwrap(),
+            Some(ConfigValue::Integer(30))
+        );
+    }
+
+    #[test]
+    fn test_appended_and_typed_flags() {
+        let app = Command::new("test")
+            .disable_help_flag(true)
+            .arg(
+                Arg::new("header")
+                    .long("header")
+                    .action(clap::ArgAction::Append),
+            )
+            .arg(
+                Arg::new("retries")
+                    .long("retries")
+                    .action(clap::ArgAction::Append)
+                    .value_parser(clap::value_parser!(u16)),
+            )
+            .arg(
+                Arg::new("config")
+                    .long("config")
+                    .value_parser(clap::value_parser!(std::path::PathBuf)),
+            );
+        let matches = app
+            .try_get_matches_from(vec![
+                "test",
+                "--header",
+                "a",
+                "--header",
+                "b",
+                "--retries",
+                "3",
+                "--config",
+                "/etc/app.toml",
+            ])
+            .unwrap();
+        let flag_layer = FlagConfigLayer::new(matches.clone());
+
+        assert_eq!(
+            flag_layer.get("header").unwrap(),
+            Some(ConfigValue::Array(vec![
+                ConfigValue::from("a"),
+                ConfigValue::from("b")
+            ]))
+        );
+        assert_eq!(
+            flag_layer.get("retries").unwrap(),
+            Some(ConfigValue::Integer(3))
+        );
+        assert_eq!(
+            flag_layer.get("config").unwrap(),
+            Some(ConfigValue::from("/etc/app.toml"))
+        );
+
+        let mut spice = crate::Spice::new();
+        spice.bind_flags(matches);
+        assert_eq!(
+            spice.get_string_slice("header").unwrap(),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_multi_value_args_given_once_stay_arrays() {
+        let app = Command::new("test")
+            .disable_help_flag(true)
+            .arg(
+                Arg::new("header")
+                    .long("header")
+                    .action(clap::ArgAction::Append),
+            )
+            .arg(Arg::new("range").long("range").num_args(2))
+            .arg(Arg::new("name").long("name"));
+        let matches = app
+            .clone()
+            .try_get_matches_from(vec!["test", "--header", "a,b", "--name", "x,y"])
+            .unwrap();
+
+        let flag_layer = FlagConfigLayer::with_command(matches.clone(), &app);
+        assert_eq!(
+            flag_layer.get("header").unwrap(),
+            Some(ConfigValue::Array(vec![ConfigValue::from("a,b")]))
+        );
+        assert_eq!(
+            flag_layer.get("name").unwrap(),
+            Some(ConfigValue::from("x,y"))
+        );
+
+        let mut spice = crate::Spice::new();
+        spice.bind_flags_with_defaults(matches, &app);
+        assert_eq!(
+            spice.get_string_slice("header").unwrap(),
+            Some(vec!["a,b".to_string()])
+        );
+
+        let matches = app
+            .clone()
+            .try_get_matches_from(vec!["test", "--range", "2", "9"])
+            .unwrap();
+        let flag_layer = FlagConfigLayer::with_command(matches, &app);
+        assert_eq!(
+            flag_layer.get("range").unwrap(),
+            Some(ConfigValue::Array(vec![
+                ConfigValue::Integer(2),
+                ConfigValue::Integer(9)
+            ]))
+        );
+    }
+
     #[test]
     fn test_read_only_layer() {
         let app = create_test_app();
@@ -595,4 +1164,134 @@ mod tests {
         assert!(keys.contains(&"verbose".to_string()));
         assert_eq!(keys.len(), 2);
     }
+
+    fn negatable_cache_layer(args: Vec<&str>) -> FlagConfigLayer {
+        let app = Command::new("test")
+            .disable_help_flag(true)
+            .arg(
+                Arg::new("cache")
+                    .long("cache")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("no-cache")
+                    .long("no-cache")
+                    .action(clap::ArgAction::SetTrue),
+            );
+        let matches = app.try_get_matches_from(args).unwrap();
+        let mut layer = FlagConfigLayer::new(matches);
+        layer.bind_negatable_flag("cache", "no-cache", "cache");
+        layer
+    }
+
+    #[test]
+    fn test_negatable_flag_positive_and_negative() {
+        let layer = negatable_cache_layer(vec!["test", "--cache"]);
+        assert_eq!(
+            layer.get("cache").unwrap(),
+            Some(ConfigValue::Boolean(true))
+        );
+
+        let layer = negatable_cache_layer(vec!["test", "--no-cache"]);
+        assert_eq!(
+            layer.get("cache").unwrap(),
+            Some(ConfigValue::Boolean(false))
+        );
+        assert_eq!(layer.get("no.cache").unwrap(), None);
+
+        // Neither flag given leaves the key to lower precedence layers
+        let layer = negatable_cache_layer(vec!["test"]);
+        assert_eq!(layer.get("cache").unwrap(), None);
+        assert!(layer.keys().is_empty());
+    }
+
+    #[test]
+    fn test_negatable_flag_last_specified_wins() {
+        let layer = negatable_cache_layer(vec!["test", "--cache", "--no-cache"]);
+        assert_eq!(
+            layer.get("cache").unwrap(),
+            Some(ConfigValue::Boolean(false))
+        );
+
+        let layer = negatable_cache_layer(vec!["test", "--no-cache", "--cache"]);
+        assert_eq!(
+            layer.get("cache").unwrap(),
+            Some(ConfigValue::Boolean(true))
+        );
+
+        let mut spice = crate::Spice::new();
+        spice
+            .set_default("cache", ConfigValue::Boolean(true))
+            .unwrap();
+        spice.add_layer(Box::new(negatable_cache_layer(vec![
+            "test",
+            "--cache",
+            "--no-cache",
+        ])));
+        assert_eq!(spice.get_bool("cache").unwrap(), Some(false));
+    }
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct IncludeConfig {
+        #[serde(default)]
+        include: Vec<String>,
+    }
+
+    fn unmarshal_includes(args: Vec<&str>) -> IncludeConfig {
+        let matches = create_test_app().try_get_matches_from(args).unwrap();
+        let mut spice = crate::Spice::new();
+        spice.set_single_value_as_array(true);
+        spice.bind_flags(matches);
+        spice.unmarshal().unwrap()
+    }
+
+    #[test]
+    fn test_append_flag_unmarshal_into_vec() {
+        // Zero occurrences fall back to the field default
+        let config = unmarshal_includes(vec!["test"]);
+        assert!(config.include.is_empty());
+
+        // A single occurrence still yields a one-element Vec
+        let config = unmarshal_includes(vec!["test", "-i", "path1"]);
+        assert_eq!(config.include, vec!["path1".to_string()]);
+
+        let config = unmarshal_includes(vec!["test", "-i", "path1", "--include", "path2"]);
+        assert_eq!(
+            config.include,
+            vec!["path1".to_string(), "path2".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_append_flag_unmarshal_into_typed_vec() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct PortConfig {
+            port: Vec<u16>,
+        }
+
+        let app = Command::new("test").disable_help_flag(true).arg(
+            Arg::new("port")
+                .long("port")
+                .action(clap::ArgAction::Append),
+        );
+
+        let matches = app
+            .clone()
+            .try_get_matches_from(vec!["test", "--port", "8080"])
+            .unwrap();
+        let mut spice = crate::Spice::new();
+        spice.set_single_value_as_array(true);
+        spice.bind_flags(matches);
+        let config: PortConfig = spice.unmarshal().unwrap();
+        assert_eq!(config.port, vec![8080]);
+
+        let matches = app
+            .try_get_matches_from(vec!["test", "--port", "8080", "--port", "9090"])
+            .unwrap();
+        let mut spice = crate::Spice::new();
+        spice.set_single_value_as_array(true);
+        spice.bind_flags(matches);
+        let config: PortConfig = spice.unmarshal().unwrap();
+        assert_eq!(config.port, vec![8080, 9090]);
+    }
 }
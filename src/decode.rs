@@ -0,0 +1,630 @@
+//! Decode hooks that reshape configuration values while unmarshaling.
+//!
+//! A decode hook sees each value together with the kind of value serde is asking for,
+//! and may replace it before it is deserialized. This lets strings such as `"30s"` fill
+//! a `Duration` field, similar to mapstructure's decode hooks in Viper.
+//!
+//! # Custom hooks
+//! A hook is any closure matching [`DecodeHook`]. It returns `Ok(Some(value))` to replace
+//! the value, `Ok(None)` to leave it to the next hook, or an error to reject it. Hooks are
+//! passed to `Spice::unmarshal_with_hooks` and tried in order; the first replacement wins.
+//!
+//! ```
+//! use spicex::decode::{DecodeHook, DecodeTarget};
+//! use spicex::{ConfigValue, Spice};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Limits {
+//!     max_upload: u64,
+//! }
+//!
+//! // Accept sizes like "10MB" for integer fields
+//! let byte_sizes: DecodeHook = Box::new(|from, to, value| {
+//!     match (from, to, value) {
+//!         ("String", DecodeTarget::Integer, ConfigValue::String(s)) => {
+//!             Ok(s.strip_suffix("MB")
+//!                 .and_then(|n| n.parse::<i64>().ok())
+//!                 .map(|n| ConfigValue::Integer(n * 1024 * 1024)))
+//!         }
+//!         _ => Ok(None),
+//!     }
+//! });
+//!
+//! let mut spice = Spice::new();
+//! spice.set("max_upload", ConfigValue::from("10MB")).unwrap();
+//! let limits: Limits = spice.unmarshal_with_hooks(&[byte_sizes]).unwrap();
+//! assert_eq!(limits.max_upload, 10 * 1024 * 1024);
+//! ```
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::value::ConfigValue;
+use std::collections::HashMap;
+
+/// The kind of value serde requests while unmarshaling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeTarget {
+    /// A boolean field
+    Bool,
+    /// An integer field of any width
+    Integer,
+    /// A floating point field
+    Float,
+    /// A string or char field
+    String,
+    /// A sequence, such as a `Vec` or tuple
+    Seq,
+    /// A map, such as a `HashMap`
+    Map,
+    /// A `std::time::Duration`, recognized by its serde name and `secs`/`nanos` fields
+    Duration,
+    /// Any other struct, identified by its type name (e.g. `"Server"`)
+    Struct(&'static str),
+}
+
+/// Hook that may transform a value before it is deserialized.
+///
+/// Called with the source type name (as returned by `ConfigValue::type_name`), the
+/// requested target, and the value itself.
+pub type DecodeHook = Box<
+    dyn Fn(&'static str, DecodeTarget, &ConfigValue) -> ConfigResult<Option<ConfigValue>>
+        + Send
+        + Sync,
+>;
+
+/// Returns a hook that decodes duration strings into `std::time::Duration` fields.
+///
+/// Strings are a sequence of numbers with units, such as `"30s"`, `"1h30m"`, or `"1.5s"`.
+/// Supported units are `ns`, `us` (or `µs`), `ms`, `s`, `m`, `h`, and `d`. Integers are
+/// read as a number of seconds.
+///
+/// # Example
+/// ```
+/// use spicex::decode;
+/// use spicex::{ConfigValue, Spice};
+/// use serde::Deserialize;
+/// use std::time::Duration;
+///
+/// #[derive(Deserialize)]
+/// struct Server {
+///     timeout: Duration,
+/// }
+///
+/// let mut spice = Spice::new();
+/// spice.set("timeout", ConfigValue::from("1m30s")).unwrap();
+/// let server: Server = spice
+///     .unmarshal_with_hooks(&[decode::string_to_duration()])
+///     .unwrap();
+/// assert_eq!(server.timeout, Duration::from_secs(90));
+/// ```
+pub fn string_to_duration() -> DecodeHook {
+    Box::new(|_from, to, value| {
+        if to != DecodeTarget::Duration {
+            return Ok(None);
+        }
+
+        let duration = match value {
+            ConfigValue::String(s) => parse_duration(s)?,
+            ConfigValue::Integer(secs) if *secs >= 0 => {
+                std::time::Duration::from_secs(*secs as u64)
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(ConfigValue::Object(HashMap::from([
+            (
+                "secs".to_string(),
+                ConfigValue::Integer(duration.as_secs() as i64),
+            ),
+            (
+                "nanos".to_string(),
+                ConfigValue::Integer(duration.subsec_nanos() as i64),
+            ),
+        ]))))
+    })
+}
+
+/// Returns a hook that splits comma-separated strings into sequences of strings.
+///
+/// Each element is trimmed and empty elements are dropped, so `"a, b,,c"` becomes
+/// `["a", "b", "c"]`.
+///
+/// # Example
+/// ```
+/// use spicex::decode;
+/// use spicex::{ConfigValue, Spice};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Cors {
+///     origins: Vec<String>,
+/// }
+///
+/// let mut spice = Spice::new();
+/// spice.set("origins", ConfigValue::from("a.com, b.com")).unwrap();
+/// let cors: Cors = spice
+///     .unmarshal_with_hooks(&[decode::comma_separated_to_vec()])
+///     .unwrap();
+/// assert_eq!(cors.origins, vec!["a.com", "b.com"]);
+/// ```
+pub fn comma_separated_to_vec() -> DecodeHook {
+    Box::new(|_from, to, value| match (to, value) {
+        (DecodeTarget::Seq, ConfigValue::String(s)) => Ok(Some(ConfigValue::Array(
+            s.split(',')
+                .map(str::trim)
+                .filter(|part| !part.is_empty())
+                .map(ConfigValue::from)
+                .collect(),
+        ))),
+        _ => Ok(None),
+    })
+}
+
+/// Returns a hook that converts between scalar types, like mapstructure's weakly typed input.
+///
+/// Strings are parsed into numbers, numbers and booleans are formatted as strings, and
+/// booleans accept anything `ConfigValue::coerce_to_bool` understands (`"1"`, `"yes"`,
+/// `0`, ...). Values that cannot be converted are left alone so the usual type error is
+/// reported.
+///
+/// # Example
+/// ```
+/// use spicex::decode;
+/// use spicex::{ConfigValue, Spice};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Server {
+///     port: u16,
+///     debug: bool,
+/// }
+///
+/// let mut spice = Spice::new();
+/// spice.set("port", ConfigValue::from("8080")).unwrap();
+/// spice.set("debug", ConfigValue::from("1")).unwrap();
+/// let server: Server = spice
+///     .unmarshal_with_hooks(&[decode::weakly_typed_input()])
+///     .unwrap();
+/// assert_eq!(server.port, 8080);
+/// assert!(server.debug);
+/// ```
+pub fn weakly_typed_input() -> DecodeHook {
+    Box::new(|_from, to, value| {
+        let coerced = match (to, value) {
+            (DecodeTarget::Bool, ConfigValue::String(_))
+            | (DecodeTarget::Bool, ConfigValue::Integer(_))
+            | (DecodeTarget::Bool, ConfigValue::Float(_)) => {
+                value.coerce_to_bool().map(ConfigValue::Boolean)
+            }
+            (DecodeTarget::Integer, ConfigValue::String(s)) => {
+                s.trim().parse::<i64>().ok().map(ConfigValue::Integer)
+            }
+            (DecodeTarget::Integer, ConfigValue::Float(f)) if f.fract() == 0.0 => {
+                Some(ConfigValue::Integer(*f as i64))
+            }
+            (DecodeTarget::Integer, ConfigValue::Boolean(b)) => {
+                Some(ConfigValue::Integer(i64::from(*b)))
+            }
+            (DecodeTarget::Float, ConfigValue::String(s)) => {
+                s.trim().parse::<f64>().ok().map(ConfigValue::Float)
+            }
+            (DecodeTarget::Float, ConfigValue::Boolean(b)) => {
+                Some(ConfigValue::Float(if *b { 1.0 } else { 0.0 }))
+            }
+            (DecodeTarget::String, ConfigValue::Integer(_))
+            | (DecodeTarget::String, ConfigValue::Float(_))
+            | (DecodeTarget::String, ConfigValue::Boolean(_)) => {
+                Some(ConfigValue::String(value.coerce_to_string()))
+            }
+            _ => None,
+        };
+        Ok(coerced)
+    })
+}
+
+/// Parses a duration string such as `"1h30m"` or `"250ms"`.
+fn parse_duration(input: &str) -> ConfigResult<std::time::Duration> {
+    let invalid = || ConfigError::invalid_value(format!("invalid duration '{input}'"));
+
+    let s = input.trim();
+    if s == "0" {
+        return Ok(std::time::Duration::ZERO);
+    }
+    if s.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total_nanos = 0f64;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(invalid)?;
+        let number: f64 = rest[..number_len].parse().map_err(|_| invalid())?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let unit_nanos = match &rest[..unit_len] {
+            "ns" => 1.0,
+            "us" | "µs" => 1e3,
+            "ms" => 1e6,
+            "s" => 1e9,
+            "m" => 60e9,
+            "h" => 3600e9,
+            "d" => 86400e9,
+            _ => return Err(invalid()),
+        };
+        rest = &rest[unit_len..];
+
+        total_nanos += number * unit_nanos;
+    }
+
+    let total_nanos = total_nanos.round();
+    if !total_nanos.is_finite() || total_nanos >= u64::MAX as f64 {
+        return Err(invalid());
+    }
+    Ok(std::time::Duration::from_nanos(total_nanos as u64))
+}
+
+/// Parses a size string such as `"256MB"` or `"1.5GiB"` into a number of bytes.
+///
+/// Decimal (`KB`, `MB`, `GB`, `TB`) and binary (`KiB`, `MiB`, `GiB`, `TiB`) suffixes are
+/// matched without regard to case, and a bare number is a count of bytes.
+pub(crate) fn parse_byte_size(input: &str) -> ConfigResult<u64> {
+    let invalid = || ConfigError::type_conversion(format!("'{input}'"), "byte size");
+
+    let s = input.trim();
+    let number_len = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let number: f64 = s[..number_len].parse().map_err(|_| invalid())?;
+
+    let multiplier: u64 = match s[number_len..].trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        _ => return Err(invalid()),
+    };
+
+    let bytes = (number * multiplier as f64).round();
+    if bytes > u64::MAX as f64 {
+        return Err(invalid());
+    }
+    Ok(bytes as u64)
+}
+
+/// Parses an RFC 3339 timestamp such as `"2024-01-01T00:00:00Z"` or
+/// `"2024-06-30T12:30:00.5+02:00"` into a point in time.
+///
+/// The date and time may be separated by `T` or a space, and the offset must be `Z` or
+/// `+HH:MM`/`-HH:MM`.
+pub(crate) fn parse_timestamp(input: &str) -> ConfigResult<std::time::SystemTime> {
+    let invalid = || ConfigError::type_conversion(format!("'{input}'"), "timestamp");
+
+    let s = input.trim();
+    if !s.is_ascii() {
+        return Err(invalid());
+    }
+    let bytes = s.as_bytes();
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return Err(invalid());
+    }
+
+    let number = |range: std::ops::Range<usize>| -> ConfigResult<i64> {
+        let digits = &s[range];
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        digits.parse().map_err(|_| invalid())
+    };
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+
+    let mut rest = &s[19..];
+    let mut nanos = 0u32;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits_len = fraction
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(fraction.len());
+        if digits_len == 0 {
+            return Err(invalid());
+        }
+        let digits = &fraction[..digits_len.min(9)];
+        nanos = digits.parse::<u32>().map_err(|_| invalid())? * 10u32.pow(9 - digits.len() as u32);
+        rest = &fraction[digits_len..];
+    }
+
+    let offset_secs = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(invalid()),
+            };
+            let offset = &rest[1..];
+            let (hours, minutes) = (&offset[..2], &offset[3..]);
+            if !(hours.bytes().chain(minutes.bytes())).all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let hours: i64 = hours.parse().map_err(|_| invalid())?;
+            let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+            if hours > 23 || minutes > 59 {
+                return Err(invalid());
+            }
+            sign * (hours * 3600 + minutes * 60)
+        }
+        _ => return Err(invalid()),
+    };
+
+    let leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap_year => 29,
+        2 => 28,
+        _ => return Err(invalid()),
+    };
+    // A leap second of 60 is accepted and folded into the following second
+    if day < 1 || day > days_in_month || hour > 23 || minute > 59 || second > 60 {
+        return Err(invalid());
+    }
+
+    let secs = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second
+        - offset_secs;
+    time_from_unix(secs, nanos).ok_or_else(invalid)
+}
+
+/// Returns the time `secs` seconds and `nanos` nanoseconds after the Unix epoch, or None
+/// if it lies outside the range `SystemTime` can represent on this platform.
+pub(crate) fn time_from_unix(secs: i64, nanos: u32) -> Option<std::time::SystemTime> {
+    let epoch = std::time::UNIX_EPOCH;
+    let whole = std::time::Duration::from_secs(secs.unsigned_abs());
+    let time = if secs >= 0 {
+        epoch.checked_add(whole)?
+    } else {
+        epoch.checked_sub(whole)?
+    };
+    time.checked_add(std::time::Duration::from_nanos(nanos as u64))
+}
+
+/// Returns the number of days from 1970-01-01 to the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Decodes base64 text using the standard or URL-safe alphabet. Padding is optional and
+/// surrounding whitespace is ignored. Returns None if the text is not valid base64.
+pub(crate) fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let trimmed = input.trim();
+    let unpadded = trimmed.trim_end_matches('=');
+    if trimmed.len() - unpadded.len() > 2 || unpadded.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(unpadded.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in unpadded.bytes() {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(sextet);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
+
+/// Decodes hexadecimal text, in either case, with an optional `0x` prefix. Surrounding
+/// whitespace is ignored. Returns None if the text is not valid hex.
+pub(crate) fn decode_hex(input: &str) -> Option<Vec<u8>> {
+    let trimmed = input.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    if !digits.len().is_multiple_of(2) || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(parse_duration("0").unwrap(), Duration::ZERO);
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("5 parsecs").is_err());
+
+        // Durations beyond u64 nanoseconds (about 584 years) are rejected, not saturated
+        assert_eq!(
+            parse_duration("100000d").unwrap(),
+            Duration::from_secs(8_640_000_000)
+        );
+        assert!(parse_duration("1000000d").is_err());
+        assert!(parse_duration(&format!("{}s", "9".repeat(400))).is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("1024").unwrap(), 1024);
+        assert_eq!(parse_byte_size("256MB").unwrap(), 256_000_000);
+        assert_eq!(parse_byte_size("64 KiB").unwrap(), 65_536);
+        assert_eq!(parse_byte_size("1.5GiB").unwrap(), 1_610_612_736);
+        assert_eq!(parse_byte_size("2gb").unwrap(), 2_000_000_000);
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("MB").is_err());
+        assert!(parse_byte_size("10 parsecs").is_err());
+        assert!(parse_byte_size("1.2.3KB").is_err());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let at = |secs: u64| std::time::UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z").unwrap(), at(0));
+        assert_eq!(
+            parse_timestamp("2024-01-01T00:00:00Z").unwrap(),
+            at(1_704_067_200)
+        );
+        assert_eq!(
+            parse_timestamp("2024-01-01 02:00:00+02:00").unwrap(),
+            at(1_704_067_200)
+        );
+        assert_eq!(
+            parse_timestamp("2024-02-29T00:00:00.25z").unwrap(),
+            at(1_709_164_800) + Duration::from_millis(250)
+        );
+        assert_eq!(
+            parse_timestamp("1969-12-31T23:59:59Z").unwrap(),
+            std::time::UNIX_EPOCH - Duration::from_secs(1)
+        );
+        assert!(parse_timestamp("2024-01-01").is_err());
+        assert!(parse_timestamp("2023-02-29T00:00:00Z").is_err());
+        assert!(parse_timestamp("2024-01-01T00:00:00").is_err());
+        assert!(parse_timestamp("2024-01-01T25:00:00Z").is_err());
+        assert!(parse_timestamp("next tuesday").is_err());
+        assert!(parse_timestamp("2024-01-01T00:00:0éZ").is_err());
+
+        // Times outside the platform's SystemTime range are errors rather than panics
+        match parse_timestamp("0001-01-01T00:00:00Z") {
+            Ok(time) => assert_eq!(Some(time), time_from_unix(-62_135_596_800, 0)),
+            Err(error) => assert!(error.is_type_conversion()),
+        }
+        for secs in [i64::MIN, i64::MAX] {
+            let _ = time_from_unix(secs, 999_999_999);
+        }
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("aGVsbG8").unwrap(), b"hello");
+        assert_eq!(decode_base64(" aGk= ").unwrap(), b"hi");
+        assert_eq!(decode_base64("").unwrap(), b"");
+        assert_eq!(decode_base64("-_8=").unwrap(), vec![0xfb, 0xff]);
+        assert_eq!(decode_base64("+/8=").unwrap(), vec![0xfb, 0xff]);
+        assert!(decode_base64("aGVsbG8===").is_none());
+        assert!(decode_base64("a").is_none());
+        assert!(decode_base64("not base64!").is_none());
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(
+            decode_hex("deadBEEF").unwrap(),
+            vec![0xde, 0xad, 0xbe, 0xef]
+        );
+        assert_eq!(decode_hex("0x00ff").unwrap(), vec![0x00, 0xff]);
+        assert_eq!(decode_hex("").unwrap(), Vec::<u8>::new());
+        assert!(decode_hex("abc").is_none());
+        assert!(decode_hex("zz").is_none());
+        assert!(decode_hex("+1").is_none());
+    }
+
+    #[test]
+    fn test_weakly_typed_input() {
+        let weak = weakly_typed_input();
+        let coerce = |to, value: ConfigValue| weak(value.type_name(), to, &value).unwrap();
+
+        assert_eq!(
+            coerce(DecodeTarget::Integer, ConfigValue::from(" 8080 ")),
+            Some(ConfigValue::Integer(8080))
+        );
+        assert_eq!(
+            coerce(DecodeTarget::Integer, ConfigValue::Float(3.0)),
+            Some(ConfigValue::Integer(3))
+        );
+        assert_eq!(coerce(DecodeTarget::Integer, ConfigValue::Float(3.5)), None);
+        assert_eq!(
+            coerce(DecodeTarget::Integer, ConfigValue::from("abc")),
+            None
+        );
+        assert_eq!(
+            coerce(DecodeTarget::Float, ConfigValue::from("1.5")),
+            Some(ConfigValue::Float(1.5))
+        );
+        assert_eq!(
+            coerce(DecodeTarget::Bool, ConfigValue::from("yes")),
+            Some(ConfigValue::Boolean(true))
+        );
+        assert_eq!(
+            coerce(DecodeTarget::Bool, ConfigValue::Integer(0)),
+            Some(ConfigValue::Boolean(false))
+        );
+        assert_eq!(coerce(DecodeTarget::Bool, ConfigValue::from("maybe")), None);
+        assert_eq!(
+            coerce(DecodeTarget::String, ConfigValue::Integer(42)),
+            Some(ConfigValue::from("42"))
+        );
+        assert_eq!(coerce(DecodeTarget::String, ConfigValue::from("x")), None);
+    }
+
+    #[test]
+    fn test_builtin_hooks_ignore_other_targets() {
+        let duration = string_to_duration();
+        let value = ConfigValue::from("30s");
+        assert_eq!(
+            duration("String", DecodeTarget::String, &value).unwrap(),
+            None
+        );
+        // A user struct that happens to be called Duration is left alone
+        assert_eq!(
+            duration("String", DecodeTarget::Struct("Duration"), &value).unwrap(),
+            None
+        );
+
+        let split = comma_separated_to_vec();
+        assert_eq!(split("String", DecodeTarget::String, &value).unwrap(), None);
+        assert_eq!(
+            split("String", DecodeTarget::Seq, &ConfigValue::from("a, b,,c")).unwrap(),
+            Some(ConfigValue::Array(vec![
+                ConfigValue::from("a"),
+                ConfigValue::from("b"),
+                ConfigValue::from("c"),
+            ]))
+        );
+    }
+}
@@ -177,6 +177,10 @@ impl ConfigLayer for DefaultConfigLayer {
         Ok(())
     }
 
+    fn unset(&mut self, key: &str) -> ConfigResult<bool> {
+        Ok(self.data.remove(key).is_some())
+    }
+
     fn keys(&self) -> Vec<String> {
         self.data.keys().cloned().collect()
     }
@@ -196,6 +200,10 @@ impl ConfigLayer for DefaultConfigLayer {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn ConfigLayer> {
+        Box::new(self.clone())
+    }
 }
 
 impl Default for DefaultConfigLayer {
@@ -205,6 +213,7 @@ impl Default for DefaultConfigLayer {
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
 
@@ -0,0 +1,962 @@
+//! Serde deserializer over `ConfigValue` that collects type errors instead of failing fast.
+
+use crate::decode::{DecodeHook, DecodeTarget};
+use crate::error::ConfigError;
+use crate::value::ConfigValue;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use std::cell::RefCell;
+use std::fmt;
+
+/// Deserializes a `ConfigValue` into `T`, reporting every field-level error found.
+///
+/// Leaf values of the wrong type are recorded together with their key path and replaced
+/// by a neutral value (zero, `false`, empty string or collection) so that deserialization
+/// can continue with the remaining fields. Errors that serde cannot recover from, such as
+/// missing required fields, are recorded as well and end deserialization of the enclosing
+/// structure.
+///
+/// # Arguments
+/// * `value` - The configuration value to deserialize
+/// * `delimiter` - The key delimiter used to build the reported key paths
+/// * `single_value_as_array` - Whether a scalar reads as a one-element sequence
+pub(crate) fn from_config_value_collecting<T>(
+    value: &ConfigValue,
+    delimiter: &str,
+    single_value_as_array: bool,
+) -> Result<T, Vec<ConfigError>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    collect_with_hooks(value, delimiter, &[], single_value_as_array)
+}
+
+/// Deserializes a `ConfigValue` into `T` with decode hooks, failing with the first error found.
+///
+/// Before a value is deserialized, each hook is offered the value and the kind of value
+/// requested; the first hook returning a replacement supplies the value instead.
+///
+/// # Arguments
+/// * `value` - The configuration value to deserialize
+/// * `delimiter` - The key delimiter used to build the reported key path
+/// * `hooks` - The decode hooks to apply, in order
+/// * `single_value_as_array` - Whether a scalar reads as a one-element sequence
+pub(crate) fn from_config_value_with_hooks<T>(
+    value: &ConfigValue,
+    delimiter: &str,
+    hooks: &[DecodeHook],
+    single_value_as_array: bool,
+) -> Result<T, ConfigError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    collect_with_hooks(value, delimiter, hooks, single_value_as_array).map_err(first_error)
+}
+
+/// Deserializes a `ConfigValue` into `T`, failing if any key does not map to a field.
+///
+/// Keys are unknown when the target struct has no field for them; their full key paths
+/// are listed in the returned `ConfigError::InvalidValue`. Other deserialization errors
+/// take precedence.
+///
+/// # Arguments
+/// * `value` - The configuration value to deserialize
+/// * `delimiter` - The key delimiter used to build the reported key paths
+/// * `single_value_as_array` - Whether a scalar reads as a one-element sequence
+pub(crate) fn from_config_value_exact<T>(
+    value: &ConfigValue,
+    delimiter: &str,
+    single_value_as_array: bool,
+) -> Result<T, ConfigError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let unknown_keys = RefCell::new(Vec::new());
+    let result = collect(value, delimiter, &[], &unknown_keys, single_value_as_array)
+        .map_err(first_error)?;
+
+    let mut unknown_keys = unknown_keys.into_inner();
+    if unknown_keys.is_empty() {
+        return Ok(result);
+    }
+    unknown_keys.sort();
+    Err(ConfigError::invalid_value(format!(
+        "unknown configuration keys: {}",
+        unknown_keys.join(", ")
+    )))
+}
+
+fn first_error(errors: Vec<ConfigError>) -> ConfigError {
+    errors
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| ConfigError::deserialization("unknown deserialization error"))
+}
+
+fn collect_with_hooks<T>(
+    value: &ConfigValue,
+    delimiter: &str,
+    hooks: &[DecodeHook],
+    single_value_as_array: bool,
+) -> Result<T, Vec<ConfigError>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    collect(
+        value,
+        delimiter,
+        hooks,
+        &RefCell::new(Vec::new()),
+        single_value_as_array,
+    )
+}
+
+fn collect<T>(
+    value: &ConfigValue,
+    delimiter: &str,
+    hooks: &[DecodeHook],
+    unknown_keys: &RefCell<Vec<String>>,
+    single_value_as_array: bool,
+) -> Result<T, Vec<ConfigError>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let errors = RefCell::new(Vec::new());
+    let deserializer = CollectingDeserializer {
+        value,
+        path: String::new(),
+        delimiter,
+        errors: &errors,
+        unknown_keys,
+        hooks,
+        hooked: false,
+        single_value_as_array,
+    };
+
+    let result = T::deserialize(deserializer);
+    let mut errors = errors.into_inner();
+
+    match result {
+        Ok(value) if errors.is_empty() => Ok(value),
+        Ok(_) | Err(DeError::Reported) => Err(errors),
+        Err(DeError::Message(message)) => {
+            errors.push(ConfigError::deserialization(message));
+            Err(errors)
+        }
+    }
+}
+
+/// Deserializes a `ConfigValue` into `T`, failing with the first error found.
+///
+/// # Arguments
+/// * `value` - The configuration value to deserialize
+/// * `delimiter` - The key delimiter used to build the reported key path
+/// * `single_value_as_array` - Whether a scalar reads as a one-element sequence
+pub(crate) fn from_config_value<T>(
+    value: &ConfigValue,
+    delimiter: &str,
+    single_value_as_array: bool,
+) -> Result<T, ConfigError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    from_config_value_with_hooks(value, delimiter, &[], single_value_as_array)
+}
+
+/// Internal error type threaded through the serde machinery.
+#[derive(Debug)]
+enum DeError {
+    /// The error has already been recorded in the error list
+    Reported,
+    /// An error raised by serde or a visitor that still needs to be recorded
+    Message(String),
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeError::Reported => write!(f, "deserialization error already reported"),
+            DeError::Message(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        DeError::Message(msg.to_string())
+    }
+}
+
+/// Deserializer for a single value, tracking its key path.
+struct CollectingDeserializer<'a> {
+    value: &'a ConfigValue,
+    path: String,
+    delimiter: &'a str,
+    errors: &'a RefCell<Vec<ConfigError>>,
+    /// Paths of values skipped because the target has no field for them
+    unknown_keys: &'a RefCell<Vec<String>>,
+    hooks: &'a [DecodeHook],
+    /// Whether `value` is already the output of a hook
+    hooked: bool,
+    /// Whether a scalar is accepted where a sequence is expected
+    single_value_as_array: bool,
+}
+
+impl<'a> CollectingDeserializer<'a> {
+    fn child(&self, segment: &str, value: &'a ConfigValue) -> Self {
+        let path = if self.path.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}{}{}", self.path, self.delimiter, segment)
+        };
+
+        Self {
+            value,
+            path,
+            delimiter: self.delimiter,
+            errors: self.errors,
+            unknown_keys: self.unknown_keys,
+            hooks: self.hooks,
+            hooked: false,
+            single_value_as_array: self.single_value_as_array,
+        }
+    }
+
+    /// Offers the current value to the decode hooks, returning the first replacement.
+    fn run_hooks(&self, target: DecodeTarget) -> Result<Option<ConfigValue>, DeError> {
+        if self.hooked {
+            return Ok(None);
+        }
+
+        for hook in self.hooks {
+            match hook(self.value.type_name(), target, self.value) {
+                Ok(Some(value)) => return Ok(Some(value)),
+                Ok(None) => {}
+                Err(e) => {
+                    self.errors
+                        .borrow_mut()
+                        .push(ConfigError::deserialization(format!(
+                            "at '{}': {}",
+                            self.display_path(),
+                            e
+                        )));
+                    return Err(DeError::Reported);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Creates a deserializer for a hook's replacement of the current value.
+    fn replaced<'b>(&'b self, value: &'b ConfigValue) -> CollectingDeserializer<'b> {
+        CollectingDeserializer {
+            value,
+            path: self.path.clone(),
+            delimiter: self.delimiter,
+            errors: self.errors,
+            unknown_keys: self.unknown_keys,
+            hooks: self.hooks,
+            hooked: true,
+            single_value_as_array: self.single_value_as_array,
+        }
+    }
+
+    fn display_path(&self) -> &str {
+        if self.path.is_empty() {
+            "<root>"
+        } else {
+            &self.path
+        }
+    }
+
+    /// Records a type mismatch for the current value.
+    fn mismatch(&self, expected: &str) {
+        let message = format!(
+            "invalid type at '{}': expected {}, found {}",
+            self.display_path(),
+            expected,
+            self.value.type_name()
+        );
+        self.errors
+            .borrow_mut()
+            .push(ConfigError::deserialization(message));
+    }
+
+    /// Records an unrecorded error with the current path and marks it as reported.
+    fn absorb(&self, error: DeError) -> DeError {
+        if let DeError::Message(message) = error {
+            self.errors
+                .borrow_mut()
+                .push(ConfigError::deserialization(format!(
+                    "at '{}': {}",
+                    self.display_path(),
+                    message
+                )));
+        }
+        DeError::Reported
+    }
+
+    fn visit_object<'de, V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            ConfigValue::Object(obj) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                let access = ObjectAccess {
+                    parent: &self,
+                    entries: entries.into_iter(),
+                    pending: None,
+                };
+                visitor.visit_map(access).map_err(|e| self.absorb(e))
+            }
+            _ => {
+                self.mismatch("object");
+                let access = ObjectAccess {
+                    parent: &self,
+                    entries: Vec::new().into_iter(),
+                    pending: None,
+                };
+                visitor.visit_map(access).map_err(|_| DeError::Reported)
+            }
+        }
+    }
+
+    fn visit_array<'de, V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            ConfigValue::Array(arr) => {
+                let access = ArrayAccess {
+                    parent: &self,
+                    elements: arr.iter().enumerate(),
+                };
+                visitor.visit_seq(access).map_err(|e| self.absorb(e))
+            }
+            // A single scalar, such as a flag given once, reads as a one-element sequence
+            ConfigValue::String(_)
+            | ConfigValue::Integer(_)
+            | ConfigValue::Float(_)
+            | ConfigValue::Boolean(_)
+                if self.single_value_as_array =>
+            {
+                let access = ArrayAccess {
+                    parent: &self,
+                    elements: std::slice::from_ref(self.value).iter().enumerate(),
+                };
+                visitor.visit_seq(access).map_err(|e| self.absorb(e))
+            }
+            _ => {
+                self.mismatch("array");
+                let access = ArrayAccess {
+                    parent: &self,
+                    elements: <&[ConfigValue]>::default().iter().enumerate(),
+                };
+                visitor.visit_seq(access).map_err(|_| DeError::Reported)
+            }
+        }
+    }
+}
+
+/// Returns early with the hook's replacement value, if any hook provides one.
+macro_rules! try_hooks {
+    ($self:ident, $target:expr, $method:ident($($arg:expr),*)) => {
+        if let Some(value) = $self.run_hooks($target)? {
+            return $self.replaced(&value).$method($($arg),*);
+        }
+    };
+}
+
+macro_rules! deserialize_integer {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, DeError>
+        where
+            V: Visitor<'de>,
+        {
+            try_hooks!(self, DecodeTarget::Integer, $method(visitor));
+            match self.value {
+                ConfigValue::Integer(i) => match <$ty>::try_from(*i) {
+                    Ok(n) => visitor.$visit(n),
+                    Err(_) => {
+                        self.errors
+                            .borrow_mut()
+                            .push(ConfigError::deserialization(format!(
+                                "invalid value at '{}': {} is out of range for {}",
+                                self.display_path(),
+                                i,
+                                stringify!($ty)
+                            )));
+                        visitor.$visit(0)
+                    }
+                },
+                _ => {
+                    self.mismatch(stringify!($ty));
+                    visitor.$visit(0)
+                }
+            }
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for CollectingDeserializer<'_> {
+    type Error = DeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            ConfigValue::String(s) => visitor.visit_str(s),
+            ConfigValue::Integer(i) => visitor.visit_i64(*i),
+            ConfigValue::Float(f) => visitor.visit_f64(*f),
+            ConfigValue::Boolean(b) => visitor.visit_bool(*b),
+            ConfigValue::Null => visitor.visit_unit(),
+            ConfigValue::Array(_) => self.visit_array(visitor),
+            ConfigValue::Object(_) => self.visit_object(visitor),
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::Bool, deserialize_bool(visitor));
+        match self.value {
+            ConfigValue::Boolean(b) => visitor.visit_bool(*b),
+            _ => {
+                self.mismatch("bool");
+                visitor.visit_bool(false)
+            }
+        }
+    }
+
+    deserialize_integer!(deserialize_i8, visit_i8, i8);
+    deserialize_integer!(deserialize_i16, visit_i16, i16);
+    deserialize_integer!(deserialize_i32, visit_i32, i32);
+    deserialize_integer!(deserialize_i64, visit_i64, i64);
+    deserialize_integer!(deserialize_u8, visit_u8, u8);
+    deserialize_integer!(deserialize_u16, visit_u16, u16);
+    deserialize_integer!(deserialize_u32, visit_u32, u32);
+    deserialize_integer!(deserialize_u64, visit_u64, u64);
+    deserialize_integer!(deserialize_u128, visit_u128, u128);
+
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::Integer, deserialize_i128(visitor));
+        match self.value {
+            ConfigValue::Integer(i) => visitor.visit_i128(i128::from(*i)),
+            _ => {
+                self.mismatch("i128");
+                visitor.visit_i128(0)
+            }
+        }
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::Float, deserialize_f64(visitor));
+        match self.value.as_f64() {
+            Some(f) => visitor.visit_f64(f),
+            None => {
+                self.mismatch("float");
+                visitor.visit_f64(0.0)
+            }
+        }
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::String, deserialize_str(visitor));
+        match self.value {
+            ConfigValue::String(s) => visitor.visit_str(s),
+            _ => {
+                self.mismatch("string");
+                visitor.visit_str("")
+            }
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            ConfigValue::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            ConfigValue::Null => visitor.visit_unit(),
+            _ => {
+                self.mismatch("null");
+                visitor.visit_unit()
+            }
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::Seq, deserialize_seq(visitor));
+        self.visit_array(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::Seq, deserialize_tuple(len, visitor));
+        self.visit_array(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(
+            self,
+            DecodeTarget::Seq,
+            deserialize_tuple_struct(name, len, visitor)
+        );
+        self.visit_array(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        try_hooks!(self, DecodeTarget::Map, deserialize_map(visitor));
+        self.visit_object(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        // serde serializes std::time::Duration as a "Duration" struct with these fields
+        let target = if name == "Duration" && fields == ["secs", "nanos"] {
+            DecodeTarget::Duration
+        } else {
+            DecodeTarget::Struct(name)
+        };
+        try_hooks!(self, target, deserialize_struct(name, fields, visitor));
+        self.visit_object(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        // Enums are rare in configuration; defer to serde_json's representation
+        let json = serde_json::to_value(self.value)
+            .map_err(|e| self.absorb(<DeError as de::Error>::custom(e)))?;
+        de::Deserializer::deserialize_enum(json, name, variants, visitor)
+            .map_err(|e| self.absorb(<DeError as de::Error>::custom(e)))
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        // Values are only ignored when the target has no field for them
+        self.unknown_keys
+            .borrow_mut()
+            .push(self.display_path().to_string());
+        visitor.visit_unit()
+    }
+}
+
+/// Map access over the entries of a `ConfigValue::Object`.
+struct ObjectAccess<'p, 'a> {
+    parent: &'p CollectingDeserializer<'a>,
+    entries: std::vec::IntoIter<(&'a String, &'a ConfigValue)>,
+    pending: Option<(&'a String, &'a ConfigValue)>,
+}
+
+impl<'de> MapAccess<'de> for ObjectAccess<'_, '_> {
+    type Error = DeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, DeError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some(entry) => {
+                self.pending = Some(entry);
+                seed.deserialize(MapKeyDeserializer { key: entry.0 })
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, DeError>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (key, value) = self
+            .pending
+            .take()
+            .ok_or_else(|| DeError::Message("value requested before key".to_string()))?;
+        let child = self.parent.child(key, value);
+        let path = child.path.clone();
+        seed.deserialize(child).map_err(|e| match e {
+            DeError::Message(message) => {
+                self.parent
+                    .errors
+                    .borrow_mut()
+                    .push(ConfigError::deserialization(format!(
+                        "at '{path}': {message}"
+                    )));
+                DeError::Reported
+            }
+            reported => reported,
+        })
+    }
+}
+
+/// Deserializer for object keys, which are always strings.
+///
+/// Like serde_json, keys deserialized as numbers or booleans are parsed from the string,
+/// so maps such as `HashMap<u16, _>` can be read from configuration objects.
+struct MapKeyDeserializer<'a> {
+    key: &'a str,
+}
+
+macro_rules! deserialize_parsed_key {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, DeError>
+        where
+            V: Visitor<'de>,
+        {
+            match self.key.parse::<$ty>() {
+                Ok(parsed) => visitor.$visit(parsed),
+                Err(_) => Err(DeError::Message(format!(
+                    "invalid key '{}': expected {}",
+                    self.key,
+                    stringify!($ty)
+                ))),
+            }
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKeyDeserializer<'_> {
+    type Error = DeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.key)
+    }
+
+    deserialize_parsed_key!(deserialize_bool, visit_bool, bool);
+    deserialize_parsed_key!(deserialize_i8, visit_i8, i8);
+    deserialize_parsed_key!(deserialize_i16, visit_i16, i16);
+    deserialize_parsed_key!(deserialize_i32, visit_i32, i32);
+    deserialize_parsed_key!(deserialize_i64, visit_i64, i64);
+    deserialize_parsed_key!(deserialize_i128, visit_i128, i128);
+    deserialize_parsed_key!(deserialize_u8, visit_u8, u8);
+    deserialize_parsed_key!(deserialize_u16, visit_u16, u16);
+    deserialize_parsed_key!(deserialize_u32, visit_u32, u32);
+    deserialize_parsed_key!(deserialize_u64, visit_u64, u64);
+    deserialize_parsed_key!(deserialize_u128, visit_u128, u128);
+    deserialize_parsed_key!(deserialize_f32, visit_f32, f32);
+    deserialize_parsed_key!(deserialize_f64, visit_f64, f64);
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_enum(self.key.into_deserializer(), name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Sequence access over the elements of a `ConfigValue::Array`.
+struct ArrayAccess<'p, 'a> {
+    parent: &'p CollectingDeserializer<'a>,
+    elements: std::iter::Enumerate<std::slice::Iter<'a, ConfigValue>>,
+}
+
+impl<'de> SeqAccess<'de> for ArrayAccess<'_, '_> {
+    type Error = DeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DeError>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.elements.next() {
+            Some((index, value)) => {
+                let child = self.parent.child(&index.to_string(), value);
+                seed.deserialize(child).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    fn object(entries: Vec<(&str, ConfigValue)>) -> ConfigValue {
+        ConfigValue::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn test_collecting_success() {
+        let value = object(vec![
+            ("host", ConfigValue::from("localhost")),
+            ("port", ConfigValue::from(8080i64)),
+        ]);
+
+        let server: Server = from_config_value_collecting(&value, ".", false).unwrap();
+        assert_eq!(server.host, "localhost");
+        assert_eq!(server.port, 8080);
+        assert!(server.tags.is_empty());
+    }
+
+    #[test]
+    fn test_collecting_reports_paths() {
+        let value = object(vec![
+            ("host", ConfigValue::from(1i64)),
+            ("port", ConfigValue::from(70000i64)),
+            (
+                "tags",
+                ConfigValue::Array(vec![ConfigValue::from("a"), ConfigValue::from(true)]),
+            ),
+        ]);
+
+        let errors = from_config_value_collecting::<Server>(&value, ".", false).unwrap_err();
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].contains("'host'"));
+        assert!(messages[1].contains("'port'") && messages[1].contains("out of range"));
+        assert!(messages[2].contains("'tags.1'"));
+    }
+
+    #[test]
+    fn test_collecting_missing_field() {
+        let value = object(vec![("host", ConfigValue::from("localhost"))]);
+
+        let errors = from_config_value_collecting::<Server>(&value, ".", false).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("missing field `port`"));
+    }
+
+    #[test]
+    fn test_exact_reports_unknown_key_paths() {
+        let value = object(vec![
+            ("host", ConfigValue::from("localhost")),
+            ("port", ConfigValue::from(8080i64)),
+            ("prot", ConfigValue::from(8081i64)),
+            ("tls", object(vec![("enabled", ConfigValue::from(true))])),
+        ]);
+
+        let error = from_config_value_exact::<Server>(&value, ".", false).unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidValue(_)));
+        assert!(error
+            .to_string()
+            .contains("unknown configuration keys: prot, tls"));
+
+        // Maps accept any key, so only struct fields are checked
+        let nested = object(vec![("primary", value)]);
+        let error =
+            from_config_value_exact::<HashMap<String, Server>>(&nested, ".", false).unwrap_err();
+        assert!(error.to_string().contains("primary.prot, primary.tls"));
+    }
+
+    #[test]
+    fn test_map_keys_parse_like_serde_json() {
+        use std::collections::BTreeMap;
+
+        fn both<T: de::DeserializeOwned>(
+            value: &ConfigValue,
+        ) -> (Result<T, String>, Result<T, String>) {
+            let json = serde_json::to_value(value).unwrap();
+            (
+                from_config_value::<T>(value, ".", false).map_err(|e| e.to_string()),
+                serde_json::from_value::<T>(json).map_err(|e| e.to_string()),
+            )
+        }
+
+        let ports = crate::config_value!({ "80": "http", "443": "https" });
+        let (ours, json) = both::<HashMap<u16, String>>(&ports);
+        assert_eq!(ours.unwrap(), json.unwrap());
+        let (ours, json) = both::<BTreeMap<i64, String>>(&ports);
+        assert_eq!(ours.unwrap(), json.unwrap());
+        let (ours, json) = both::<BTreeMap<u128, String>>(&ports);
+        assert_eq!(ours.unwrap(), json.unwrap());
+
+        let mut spice = crate::Spice::new();
+        spice.set("ports", ports).unwrap();
+        let unmarshalled: HashMap<u16, String> = spice.unmarshal_key("ports").unwrap();
+        assert_eq!(unmarshalled[&443], "https");
+
+        let flags = crate::config_value!({ "true": 1, "false": 0 });
+        let (ours, json) = both::<HashMap<bool, i128>>(&flags);
+        assert_eq!(ours.unwrap(), json.unwrap());
+
+        // Keys that do not parse fail on both paths
+        let (ours, json) = both::<HashMap<u16, String>>(&crate::config_value!({ "http": "80" }));
+        assert!(ours.unwrap_err().contains("invalid key 'http'"));
+        assert!(json.is_err());
+    }
+
+    #[test]
+    fn test_scalar_reads_as_single_element_sequence() {
+        let value = object(vec![
+            ("host", ConfigValue::from("localhost")),
+            ("port", ConfigValue::from(8080i64)),
+            ("tags", ConfigValue::from("primary")),
+        ]);
+
+        let server: Server = from_config_value(&value, ".", true).unwrap();
+        assert_eq!(server.tags, vec!["primary".to_string()]);
+
+        // Without the option a scalar is a type error
+        let error = from_config_value::<Server>(&value, ".", false).unwrap_err();
+        assert!(error.to_string().contains("expected array"));
+
+        let error = from_config_value::<Vec<String>>(&ConfigValue::Null, ".", true).unwrap_err();
+        assert!(error.to_string().contains("expected array"));
+    }
+}
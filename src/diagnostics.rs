@@ -0,0 +1,141 @@
+//! Configuration health checks reported by `Spice::diagnose`.
+
+use crate::value::ConfigValue;
+use std::fmt;
+
+/// Key fragments that suggest a value is a credential.
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "private_key",
+];
+
+/// How serious a diagnostic finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Informational finding that needs no action
+    Info,
+    /// Likely misconfiguration worth reviewing
+    Warning,
+    /// Configuration that cannot be used as-is
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single finding produced by `Spice::diagnose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How serious the finding is
+    pub severity: Severity,
+    /// Human-readable description of the finding
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic with the given severity and message.
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an informational diagnostic.
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(Severity::Info, message)
+    }
+
+    /// Creates a warning diagnostic.
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// Creates an error diagnostic.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+/// Returns true if the last segment of `key` looks like it names a credential.
+pub(crate) fn is_secret_key(key: &str, delimiter: &str) -> bool {
+    let last = key.rsplit(delimiter).next().unwrap_or(key).to_lowercase();
+    SECRET_KEY_MARKERS
+        .iter()
+        .any(|marker| last.contains(marker))
+}
+
+/// Collects the dotted path of every leaf value below `key`.
+pub(crate) fn collect_leaf_paths(
+    key: &str,
+    value: &ConfigValue,
+    delimiter: &str,
+    out: &mut Vec<(String, ConfigValue)>,
+) {
+    match value {
+        ConfigValue::Object(obj) if !obj.is_empty() => {
+            for (child_key, child_value) in obj {
+                let path = format!("{key}{delimiter}{child_key}");
+                collect_leaf_paths(&path, child_value, delimiter, out);
+            }
+        }
+        _ => out.push((key.to_string(), value.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_value;
+
+    #[test]
+    fn test_is_secret_key() {
+        assert!(is_secret_key("database.password", "."));
+        assert!(is_secret_key("github.api_key", "."));
+        assert!(is_secret_key("AUTH_TOKEN", "."));
+        assert!(!is_secret_key("password_policy.length", "."));
+        assert!(!is_secret_key("database.host", "."));
+    }
+
+    #[test]
+    fn test_collect_leaf_paths() {
+        let mut leaves = Vec::new();
+        collect_leaf_paths(
+            "database",
+            &config_value!({ "host": "localhost", "pool": { "max": 10 } }),
+            ".",
+            &mut leaves,
+        );
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            leaves,
+            vec![
+                ("database.host".to_string(), config_value!("localhost")),
+                ("database.pool.max".to_string(), config_value!(10)),
+            ]
+        );
+        assert_eq!(
+            Diagnostic::warning("check me").to_string(),
+            "warning: check me"
+        );
+    }
+}
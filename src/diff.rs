@@ -0,0 +1,138 @@
+//! Differences between two states of the merged configuration.
+
+use crate::value::ConfigValue;
+use std::collections::HashMap;
+
+/// The keys whose values differ between two states of the configuration, such as before
+/// and after a reload.
+///
+/// Keys are flattened leaf paths (e.g. `server.port`) and each list is sorted by key.
+/// Unlike `ConfigChange`, which names the files that changed on disk, a `ConfigDiff`
+/// describes the effect on the values themselves.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConfigDiff {
+    /// Keys that only exist in the new configuration, with their values
+    pub added: Vec<(String, ConfigValue)>,
+    /// Keys that only exist in the old configuration, with their old values
+    pub removed: Vec<(String, ConfigValue)>,
+    /// Keys present in both whose values differ, with the old and new values
+    pub modified: Vec<(String, ConfigValue, ConfigValue)>,
+    delimiter: String,
+}
+
+impl ConfigDiff {
+    /// Compares two flattened settings maps.
+    pub(crate) fn between(
+        old: &HashMap<String, ConfigValue>,
+        new: &HashMap<String, ConfigValue>,
+        delimiter: &str,
+    ) -> Self {
+        let mut diff = Self {
+            delimiter: delimiter.to_string(),
+            ..Self::default()
+        };
+
+        for (key, new_value) in new {
+            match old.get(key) {
+                None => diff.added.push((key.clone(), new_value.clone())),
+                Some(old_value) if old_value != new_value => {
+                    diff.modified
+                        .push((key.clone(), old_value.clone(), new_value.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, old_value) in old {
+            if !new.contains_key(key) {
+                diff.removed.push((key.clone(), old_value.clone()));
+            }
+        }
+
+        diff.added.sort_by(|a, b| a.0.cmp(&b.0));
+        diff.removed.sort_by(|a, b| a.0.cmp(&b.0));
+        diff.modified.sort_by(|a, b| a.0.cmp(&b.0));
+        diff
+    }
+
+    /// Returns true if no key was added, removed or modified.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Returns every changed key in sorted order.
+    pub fn changed_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .added
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .chain(self.removed.iter().map(|(key, _)| key.as_str()))
+            .chain(self.modified.iter().map(|(key, _, _)| key.as_str()))
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Returns true if `key`, or any key nested under it, changed.
+    ///
+    /// # Arguments
+    /// * `key` - A leaf key such as `server.port`, or a section such as `server`
+    pub fn contains(&self, key: &str) -> bool {
+        self.changed_keys().into_iter().any(|changed| {
+            changed == key
+                || changed
+                    .strip_prefix(key)
+                    .is_some_and(|rest| rest.starts_with(self.delimiter.as_str()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_between_settings() {
+        let old: HashMap<String, ConfigValue> = [
+            ("server.port".to_string(), ConfigValue::from(8080i64)),
+            ("server.host".to_string(), ConfigValue::from("localhost")),
+            ("debug".to_string(), ConfigValue::from(true)),
+        ]
+        .into_iter()
+        .collect();
+        let new: HashMap<String, ConfigValue> = [
+            ("server.port".to_string(), ConfigValue::from(9090i64)),
+            ("server.host".to_string(), ConfigValue::from("localhost")),
+            ("server.tls".to_string(), ConfigValue::from(false)),
+        ]
+        .into_iter()
+        .collect();
+
+        let diff = ConfigDiff::between(&old, &new, ".");
+        assert_eq!(
+            diff.added,
+            vec![("server.tls".to_string(), ConfigValue::from(false))]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![("debug".to_string(), ConfigValue::from(true))]
+        );
+        assert_eq!(
+            diff.modified,
+            vec![(
+                "server.port".to_string(),
+                ConfigValue::from(8080i64),
+                ConfigValue::from(9090i64)
+            )]
+        );
+        assert_eq!(
+            diff.changed_keys(),
+            vec!["debug", "server.port", "server.tls"]
+        );
+        assert!(diff.contains("server"));
+        assert!(diff.contains("server.port"));
+        assert!(!diff.contains("server.host"));
+        assert!(!diff.contains("serv"));
+        assert!(!diff.is_empty());
+        assert!(ConfigDiff::between(&old, &old, ".").is_empty());
+    }
+}
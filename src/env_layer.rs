@@ -1,27 +1,220 @@
 //! Environment variable configuration layer implementation.
 
-use crate::error::ConfigResult;
+use crate::error::{ConfigError, ConfigResult};
 use crate::layer::{ConfigLayer, LayerPriority};
 use crate::value::ConfigValue;
 use std::collections::HashMap;
 use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Type alias for functions that transform configuration keys into env var names.
+type KeyReplacer = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Source of environment variables read by `EnvConfigLayer`.
+///
+/// The default source is the process environment. Supplying a different source
+/// lets tests exercise the environment layer without touching global process state.
+pub trait EnvSource: Send + Sync {
+    /// Returns the value of a single variable, if set.
+    fn var(&self, name: &str) -> Option<String>;
+
+    /// Returns all variables in this source.
+    fn vars(&self) -> Vec<(String, String)>;
+
+    /// Returns true if variables may change between reads, as process environment
+    /// variables can. Sources that never change return false so their values can be cached.
+    fn is_volatile(&self) -> bool {
+        true
+    }
+}
+
+/// Environment source backed by the process environment.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnv;
+
+impl EnvSource for ProcessEnv {
+    fn var(&self, name: &str) -> Option<String> {
+        env::var(name).ok()
+    }
+
+    fn vars(&self) -> Vec<(String, String)> {
+        env::vars().collect()
+    }
+}
+
+/// In-memory environment source, useful for deterministic tests.
+///
+/// # Example
+/// ```
+/// use spicex::env_layer::{EnvConfigLayer, MapEnv};
+/// use spicex::{ConfigLayer, ConfigValue};
+///
+/// let env = MapEnv::new().with("APP_DATABASE_HOST", "localhost");
+/// let layer = EnvConfigLayer::with_source(Some("APP".to_string()), false, env);
+/// assert_eq!(
+///     layer.get("database.host").unwrap(),
+///     Some(ConfigValue::from("localhost"))
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MapEnv {
+    vars: HashMap<String, String>,
+}
+
+impl MapEnv {
+    /// Creates an empty environment.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a variable to the environment, returning the updated environment.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+}
+
+impl From<HashMap<String, String>> for MapEnv {
+    fn from(vars: HashMap<String, String>) -> Self {
+        Self { vars }
+    }
+}
+
+impl EnvSource for MapEnv {
+    fn var(&self, name: &str) -> Option<String> {
+        self.vars.get(name).cloned()
+    }
+
+    fn vars(&self) -> Vec<(String, String)> {
+        self.vars
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    fn is_volatile(&self) -> bool {
+        false
+    }
+}
+
+/// Lock serializing process environment changes made through `EnvGuard`.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Whether a guard on this thread holds `ENV_LOCK`, so nested guards do not wait on it.
+    static ENV_LOCK_HELD: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Scoped modification of process environment variables.
+///
+/// Variables set or removed through the guard are restored to their previous state
+/// when it is dropped. Guards hold a global lock, so tests using them do not race on
+/// the process environment even when run in parallel. A guard created while another
+/// guard on the same thread is alive shares that guard's lock instead of waiting for
+/// it; drop nested guards before the outer one.
+///
+/// # Example
+/// ```
+/// use spicex::env_layer::EnvGuard;
+///
+/// {
+///     let mut guard = EnvGuard::new();
+///     guard.set("SPICEX_GUARD_EXAMPLE", "1");
+///     assert_eq!(std::env::var("SPICEX_GUARD_EXAMPLE").unwrap(), "1");
+/// }
+/// assert!(std::env::var("SPICEX_GUARD_EXAMPLE").is_err());
+/// ```
+pub struct EnvGuard {
+    /// Original values of modified variables, in modification order
+    saved: Vec<(String, Option<String>)>,
+    /// Held for the lifetime of the guard, unless an outer guard on this thread holds it
+    lock: Option<MutexGuard<'static, ()>>,
+}
+
+impl EnvGuard {
+    /// Acquires the environment lock, unless this thread already holds it through another
+    /// guard, and creates an empty guard.
+    pub fn new() -> Self {
+        let lock = if ENV_LOCK_HELD.with(|held| held.get()) {
+            None
+        } else {
+            let lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            ENV_LOCK_HELD.with(|held| held.set(true));
+            Some(lock)
+        };
+        Self {
+            saved: Vec::new(),
+            lock,
+        }
+    }
+
+    /// Sets a variable for the lifetime of the guard.
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.saved.push((name.to_string(), env::var(name).ok()));
+        env::set_var(name, value);
+    }
+
+    /// Removes a variable for the lifetime of the guard.
+    pub fn remove(&mut self, name: &str) {
+        self.saved.push((name.to_string(), env::var(name).ok()));
+        env::remove_var(name);
+    }
+}
+
+impl Default for EnvGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        // Restore in reverse so repeated changes to one variable unwind correctly
+        for (name, value) in self.saved.drain(..).rev() {
+            match value {
+                Some(value) => env::set_var(&name, value),
+                None => env::remove_var(&name),
+            }
+        }
+        if self.lock.take().is_some() {
+            ENV_LOCK_HELD.with(|held| held.set(false));
+        }
+    }
+}
 
 /// Configuration layer that reads from environment variables.
 ///
 /// This layer provides automatic environment variable discovery and key transformation
 /// to support various naming conventions and nested structures.
+#[derive(Clone)]
 pub struct EnvConfigLayer {
     /// Optional prefix for environment variable names
     prefix: Option<String>,
 
     /// Custom key transformation function
-    key_replacer: Option<Box<dyn Fn(&str) -> String + Send + Sync>>,
+    key_replacer: Option<KeyReplacer>,
 
     /// Cached environment variables for performance
     cached_vars: HashMap<String, String>,
 
     /// Whether to automatically discover environment variables
     automatic: bool,
+
+    /// Where environment variables are read from
+    source: Arc<dyn EnvSource>,
+
+    /// Separator used to split values into arrays, or None to keep single strings
+    list_separator: Option<String>,
+
+    /// Key subtrees read from variables with their own prefix, as (key prefix, env prefix)
+    scopes: Vec<(String, String)>,
+
+    /// Whether keys outside every scope are left to other layers
+    scoped_only: bool,
+
+    /// The `.env` file the variables were read from, which keeps the layer's own source
+    dotenv_path: Option<PathBuf>,
 }
 
 impl EnvConfigLayer {
@@ -42,11 +235,31 @@ impl EnvConfigLayer {
     /// let env_layer = EnvConfigLayer::new(None, false);
     /// ```
     pub fn new(prefix: Option<String>, automatic: bool) -> Self {
+        Self::with_source(prefix, automatic, ProcessEnv)
+    }
+
+    /// Creates a new environment variable configuration layer reading from `source`
+    /// instead of the process environment.
+    ///
+    /// # Arguments
+    /// * `prefix` - Optional prefix to filter environment variables
+    /// * `automatic` - Whether to automatically discover all matching environment variables
+    /// * `source` - The environment to read variables from
+    pub fn with_source(
+        prefix: Option<String>,
+        automatic: bool,
+        source: impl EnvSource + 'static,
+    ) -> Self {
         let mut layer = Self {
             prefix,
             key_replacer: None,
             cached_vars: HashMap::new(),
             automatic,
+            source: Arc::new(source),
+            list_separator: None,
+            scopes: Vec::new(),
+            scoped_only: false,
+            dotenv_path: None,
         };
 
         if automatic {
@@ -56,6 +269,172 @@ impl EnvConfigLayer {
         layer
     }
 
+    /// Creates a new environment variable configuration layer over a fixed set of
+    /// variables instead of the process environment.
+    ///
+    /// The layer discovers variables automatically, so every matching entry in `vars` is
+    /// reported by `keys`.
+    ///
+    /// # Arguments
+    /// * `prefix` - Optional prefix to filter environment variables
+    /// * `vars` - The variables to read, keyed by variable name
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::env_layer::EnvConfigLayer;
+    /// use spicex::{ConfigLayer, ConfigValue};
+    /// use std::collections::HashMap;
+    ///
+    /// let vars = HashMap::from([("APP_PORT".to_string(), "8080".to_string())]);
+    /// let layer = EnvConfigLayer::from_map(Some("APP".to_string()), vars);
+    /// assert_eq!(layer.get("port").unwrap(), Some(ConfigValue::from(8080i64)));
+    /// assert_eq!(layer.keys(), vec!["port".to_string()]);
+    /// ```
+    pub fn from_map(prefix: Option<String>, vars: HashMap<String, String>) -> Self {
+        Self::with_source(prefix, true, MapEnv::from(vars))
+    }
+
+    /// Creates a layer over the variables defined in a `.env` file. Variables are
+    /// matched and normalized like process environment variables discovered
+    /// automatically: `APP_DATABASE_HOST=localhost` with prefix `APP` reads as
+    /// `database.host`.
+    ///
+    /// Lines have the form `KEY=value`, optionally preceded by `export`. Blank lines
+    /// and lines starting with `#` are skipped. Values may be single-quoted (taken
+    /// literally), double-quoted (supporting `\n`, `\t`, `\"` and `\\` escapes) or
+    /// bare, in which case a ` #` starts a comment.
+    ///
+    /// # Arguments
+    /// * `prefix` - Optional prefix to filter variables
+    /// * `path` - The `.env` file to read
+    ///
+    /// # Errors
+    /// * `ConfigError::Io` - If the file cannot be read
+    /// * `ConfigError::Parse` - If a line is not a valid assignment
+    pub fn from_dotenv<P: AsRef<Path>>(prefix: Option<String>, path: P) -> ConfigResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let vars = parse_dotenv(&content, &path.display().to_string())?;
+        let mut layer = Self::from_map(prefix, vars);
+        layer.dotenv_path = Some(path.to_path_buf());
+        Ok(layer)
+    }
+
+    /// Returns the `.env` file the layer was read from, if it was created with
+    /// `from_dotenv`.
+    pub fn dotenv_path(&self) -> Option<&Path> {
+        self.dotenv_path.as_deref()
+    }
+
+    /// Creates a layer that reads only the keys under `key_prefix`, from variables named
+    /// with `env_prefix`. Other keys are left to other layers. More subtrees can be added
+    /// with `bind_prefix`.
+    ///
+    /// # Arguments
+    /// * `key_prefix` - The configuration key whose subtree is read from the environment
+    /// * `env_prefix` - The prefix of the variables backing the subtree
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::env_layer::{EnvConfigLayer, MapEnv};
+    /// use spicex::{ConfigLayer, ConfigValue};
+    ///
+    /// let mut env_layer = EnvConfigLayer::scoped("database", "DB");
+    /// env_layer.set_source(std::sync::Arc::new(
+    ///     MapEnv::new().with("DB_HOST", "db.internal").with("HOST", "ignored"),
+    /// ));
+    ///
+    /// assert_eq!(
+    ///     env_layer.get("database.host").unwrap(),
+    ///     Some(ConfigValue::from("db.internal"))
+    /// );
+    /// assert_eq!(env_layer.get("host").unwrap(), None);
+    /// ```
+    pub fn scoped(key_prefix: &str, env_prefix: &str) -> Self {
+        let mut layer = Self::new(None, false);
+        layer.scoped_only = true;
+        layer.bind_prefix(key_prefix, env_prefix);
+        layer
+    }
+
+    /// Reads the keys under `key_prefix` from variables named with `env_prefix`, so
+    /// `database.host` under `("database", "DB")` reads `DB_HOST`. The key replacer
+    /// applies to the part of the variable name after the prefix. When subtrees overlap,
+    /// the longest key prefix wins.
+    ///
+    /// # Arguments
+    /// * `key_prefix` - The configuration key whose subtree is read from the environment
+    /// * `env_prefix` - The prefix of the variables backing the subtree
+    pub fn bind_prefix(&mut self, key_prefix: &str, env_prefix: &str) {
+        let key_prefix = key_prefix.trim_end_matches('.').to_string();
+        let env_prefix = env_prefix.trim_end_matches('_').to_string();
+        self.scopes.retain(|(existing, _)| *existing != key_prefix);
+        self.scopes.push((key_prefix, env_prefix));
+        // Longest key prefix first, so nested bindings take precedence
+        self.scopes
+            .sort_by_key(|(key_prefix, _)| std::cmp::Reverse(key_prefix.len()));
+    }
+
+    /// Returns true if the layer reads only the subtrees bound with `bind_prefix`.
+    pub fn is_scoped_only(&self) -> bool {
+        self.scoped_only
+    }
+
+    /// Returns the environment variable name this layer reads for `key`.
+    /// Keys under a subtree bound with `bind_prefix` use that subtree's prefix; other
+    /// keys use `transform_key`, or yield None when the layer is scoped only.
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::env_layer::EnvConfigLayer;
+    ///
+    /// let mut env_layer = EnvConfigLayer::new(Some("APP".to_string()), false);
+    /// env_layer.bind_prefix("database", "DB");
+    /// assert_eq!(env_layer.env_var_name("database.host"), Some("DB_HOST".to_string()));
+    /// assert_eq!(env_layer.env_var_name("server.port"), Some("APP_SERVER_PORT".to_string()));
+    /// ```
+    pub fn env_var_name(&self, key: &str) -> Option<String> {
+        match self.scoped_var_name(key) {
+            Some(name) => Some(name),
+            None if self.scoped_only => None,
+            None => Some(self.transform_key(key)),
+        }
+    }
+
+    /// Returns the variable name backing `key` if it lies under a bound subtree.
+    fn scoped_var_name(&self, key: &str) -> Option<String> {
+        self.scopes.iter().find_map(|(key_prefix, env_prefix)| {
+            let rest = key.strip_prefix(key_prefix.as_str())?.strip_prefix('.')?;
+            let mut env_key = rest.to_uppercase().replace(".", "_");
+            if let Some(ref replacer) = self.key_replacer {
+                env_key = replacer(&env_key);
+            }
+            Some(format!("{env_prefix}_{env_key}"))
+        })
+    }
+
+    /// Replaces the source environment variables are read from, refreshing the cache
+    /// if automatic discovery is enabled.
+    ///
+    /// # Arguments
+    /// * `source` - The environment to read variables from
+    pub fn set_source(&mut self, source: Arc<dyn EnvSource>) {
+        self.source = source;
+        if self.automatic {
+            self.refresh_cache();
+        }
+    }
+
+    /// Uses the same key replacement function as `other`.
+    pub(crate) fn share_key_replacer(&mut self, other: &EnvConfigLayer) {
+        self.key_replacer = other.key_replacer.clone();
+    }
+
+    /// Returns the prefix variables must start with, if any.
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
     /// Sets a custom key replacement function for transforming configuration keys
     /// to environment variable names.
     ///
@@ -75,7 +454,42 @@ impl EnvConfigLayer {
     where
         F: Fn(&str) -> String + Send + Sync + 'static,
     {
-        self.key_replacer = Some(replacer);
+        self.key_replacer = Some(Arc::from(replacer as Box<_>));
+    }
+
+    /// Sets the separator used to split environment variable values into arrays.
+    ///
+    /// When set, every matched variable is returned as a `ConfigValue::Array`, with each
+    /// trimmed element parsed like a single value. A value without the separator becomes
+    /// a one-element array. `None` (the default) returns values unsplit.
+    ///
+    /// # Arguments
+    /// * `separator` - The list separator, or None to disable splitting
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::env_layer::{EnvConfigLayer, MapEnv};
+    /// use spicex::{ConfigLayer, ConfigValue};
+    ///
+    /// let env = MapEnv::new().with("APP_HOSTS", "a.com, b.com");
+    /// let mut env_layer = EnvConfigLayer::with_source(Some("APP".to_string()), false, env);
+    /// env_layer.set_list_separator(Some(",".to_string()));
+    ///
+    /// assert_eq!(
+    ///     env_layer.get("hosts").unwrap(),
+    ///     Some(ConfigValue::Array(vec![
+    ///         ConfigValue::from("a.com"),
+    ///         ConfigValue::from("b.com"),
+    ///     ]))
+    /// );
+    /// ```
+    pub fn set_list_separator(&mut self, separator: Option<String>) {
+        self.list_separator = separator;
+    }
+
+    /// Gets the separator used to split values into arrays, if any.
+    pub fn list_separator(&self) -> Option<&str> {
+        self.list_separator.as_deref()
     }
 
     /// Refreshes the cached environment variables.
@@ -83,7 +497,7 @@ impl EnvConfigLayer {
     pub fn refresh_cache(&mut self) {
         self.cached_vars.clear();
 
-        for (key, value) in env::vars() {
+        for (key, value) in self.source.vars() {
             if let Some(ref prefix) = self.prefix {
                 if key.starts_with(&format!("{prefix}_")) {
                     // Remove prefix and convert to config key format
@@ -149,7 +563,7 @@ impl EnvConfigLayer {
     /// # Returns
     /// The environment variable value wrapped in ConfigValue::String, or None if not found
     fn get_env_var(&self, env_var_name: &str) -> Option<ConfigValue> {
-        env::var(env_var_name).ok().map(ConfigValue::String)
+        self.source.var(env_var_name).map(ConfigValue::String)
     }
 
     /// Attempts to parse a string value into a more specific ConfigValue type.
@@ -183,23 +597,43 @@ impl EnvConfigLayer {
         // Default to string
         ConfigValue::String(value)
     }
+
+    /// Converts a raw variable value, splitting it into an array if a separator is set.
+    fn to_config_value(&self, value: String) -> ConfigValue {
+        match self.list_separator.as_deref() {
+            Some(separator) if !separator.is_empty() => ConfigValue::Array(
+                value
+                    .split(separator)
+                    .map(|part| self.parse_env_value(part.trim().to_string()))
+                    .collect(),
+            ),
+            _ => self.parse_env_value(value),
+        }
+    }
 }
 
 impl ConfigLayer for EnvConfigLayer {
     fn get(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
+        if let Some(env_var_name) = self.scoped_var_name(key) {
+            if let Some(value) = self.source.var(&env_var_name) {
+                return Ok(Some(self.to_config_value(value)));
+            }
+        }
+        if self.scoped_only {
+            return Ok(None);
+        }
+
         // First check cached vars if automatic mode is enabled
         if self.automatic {
             if let Some(value) = self.cached_vars.get(key) {
-                return Ok(Some(self.parse_env_value(value.clone())));
+                return Ok(Some(self.to_config_value(value.clone())));
             }
         }
 
         // Transform the key to environment variable format and check directly
         let env_var_name = self.transform_key(key);
-        if let Some(value) = self.get_env_var(&env_var_name) {
-            if let ConfigValue::String(s) = value {
-                return Ok(Some(self.parse_env_value(s)));
-            }
+        if let Some(ConfigValue::String(s)) = self.get_env_var(&env_var_name) {
+            return Ok(Some(self.to_config_value(s)));
         }
 
         Ok(None)
@@ -216,23 +650,46 @@ impl ConfigLayer for EnvConfigLayer {
     }
 
     fn keys(&self) -> Vec<String> {
-        if self.automatic {
+        let mut keys = if self.automatic {
             self.cached_vars.keys().cloned().collect()
         } else {
             // In non-automatic mode, we can't enumerate all possible keys
             // since we don't know what environment variables exist
             Vec::new()
+        };
+
+        // Variables of bound subtrees can be enumerated by their prefix
+        if !self.scopes.is_empty() {
+            for (name, _) in self.source.vars() {
+                for (key_prefix, env_prefix) in &self.scopes {
+                    if let Some(rest) = name.strip_prefix(&format!("{env_prefix}_")) {
+                        let key = format!("{key_prefix}.{}", rest.to_lowercase().replace("_", "."));
+                        if !keys.contains(&key) {
+                            keys.push(key);
+                        }
+                    }
+                }
+            }
         }
+        keys
     }
 
     fn source_name(&self) -> &str {
-        "environment variables"
+        if self.dotenv_path.is_some() {
+            ".env file"
+        } else {
+            "environment variables"
+        }
     }
 
     fn priority(&self) -> LayerPriority {
         LayerPriority::Environment
     }
 
+    fn is_volatile(&self) -> bool {
+        self.source.is_volatile()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -240,9 +697,84 @@ impl ConfigLayer for EnvConfigLayer {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn ConfigLayer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Parses the `KEY=value` assignments of a `.env` file.
+pub(crate) fn parse_dotenv(
+    content: &str,
+    source_name: &str,
+) -> ConfigResult<HashMap<String, String>> {
+    let mut vars = HashMap::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: &str| {
+            ConfigError::parse_error(source_name, format!("line {}: {message}", number + 1))
+        };
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, raw) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected KEY=value"))?;
+        let name = name.trim();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            return Err(error(&format!("invalid variable name '{name}'")));
+        }
+
+        let raw = raw.trim_start();
+        let (value, rest) = if let Some(quoted) = raw.strip_prefix('\'') {
+            let end = quoted
+                .find('\'')
+                .ok_or_else(|| error("unterminated single quote"))?;
+            (quoted[..end].to_string(), &quoted[end + 1..])
+        } else if let Some(quoted) = raw.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            let end = loop {
+                match chars.next() {
+                    Some((i, '"')) => break i,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => value.push('\n'),
+                        Some((_, 't')) => value.push('\t'),
+                        Some((_, 'r')) => value.push('\r'),
+                        Some((_, c)) => value.push(c),
+                        None => return Err(error("unterminated double quote")),
+                    },
+                    Some((_, c)) => value.push(c),
+                    None => return Err(error("unterminated double quote")),
+                }
+            };
+            (value, &quoted[end + 1..])
+        } else {
+            let value = match raw.find(" #").or_else(|| raw.find("\t#")) {
+                Some(comment) => &raw[..comment],
+                None if raw.starts_with('#') => "",
+                None => raw,
+            };
+            (value.trim_end().to_string(), "")
+        };
+
+        let rest = rest.trim_start();
+        if !rest.is_empty() && !rest.starts_with('#') {
+            return Err(error("unexpected text after quoted value"));
+        }
+        vars.insert(name.to_string(), value);
+    }
+    Ok(vars)
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
     use std::env;
@@ -291,17 +823,212 @@ mod tests {
 
     #[test]
     fn test_get_environment_variable() {
-        // Set a test environment variable
-        env::set_var("TEST_ENV_VAR", "test_value");
+        // Set a test environment variable for the duration of the guard
+        let mut guard = EnvGuard::new();
+        guard.set("TEST_ENV_VAR", "test_value");
 
         let env_layer = EnvConfigLayer::new(Some("TEST".to_string()), false);
 
         // Should find the environment variable
         let result = env_layer.get("env.var").unwrap();
         assert_eq!(result, Some(ConfigValue::String("test_value".to_string())));
+    }
 
-        // Clean up
-        env::remove_var("TEST_ENV_VAR");
+    #[test]
+    fn test_env_guard_restores_variables() {
+        let mut outer = EnvGuard::new();
+        outer.set("GUARD_TEST_EXISTING", "original");
+
+        // Guards nested on one thread share the outer guard's lock
+        {
+            let mut guard = EnvGuard::new();
+            guard.set("GUARD_TEST_EXISTING", "changed");
+            guard.set("GUARD_TEST_NEW", "added");
+            guard.set("GUARD_TEST_NEW", "added twice");
+            assert_eq!(env::var("GUARD_TEST_EXISTING").unwrap(), "changed");
+            assert_eq!(env::var("GUARD_TEST_NEW").unwrap(), "added twice");
+        }
+        assert_eq!(env::var("GUARD_TEST_EXISTING").unwrap(), "original");
+        assert!(env::var("GUARD_TEST_NEW").is_err());
+
+        {
+            let mut guard = EnvGuard::new();
+            guard.remove("GUARD_TEST_EXISTING");
+            assert!(env::var("GUARD_TEST_EXISTING").is_err());
+        }
+        assert_eq!(env::var("GUARD_TEST_EXISTING").unwrap(), "original");
+        drop(outer);
+        assert!(env::var("GUARD_TEST_EXISTING").is_err());
+
+        // The lock is released once the outer guard is dropped
+        let handle = std::thread::spawn(|| {
+            let mut guard = EnvGuard::new();
+            guard.set("GUARD_TEST_THREAD", "set");
+        });
+        handle.join().unwrap();
+        assert!(env::var("GUARD_TEST_THREAD").is_err());
+    }
+
+    #[test]
+    fn test_injected_source_is_isolated_from_process_env() {
+        let source = MapEnv::new()
+            .with("INJECTED_DATABASE_HOST", "localhost")
+            .with("INJECTED_DATABASE_PORT", "5432");
+        let env_layer = EnvConfigLayer::with_source(Some("INJECTED".to_string()), false, source);
+
+        assert_eq!(
+            env_layer.get("database.host").unwrap(),
+            Some(ConfigValue::from("localhost"))
+        );
+        assert_eq!(
+            env_layer.get("database.port").unwrap(),
+            Some(ConfigValue::Integer(5432))
+        );
+        assert_eq!(env_layer.get("path").unwrap(), None);
+
+        // Cloned layers share the injected source
+        let cloned = env_layer.clone();
+        assert_eq!(
+            cloned.get("database.host").unwrap(),
+            Some(ConfigValue::from("localhost"))
+        );
+    }
+
+    #[test]
+    fn test_from_map_reads_only_the_given_variables() {
+        let vars = HashMap::from([
+            ("MAP_DATABASE_HOST".to_string(), "localhost".to_string()),
+            ("OTHER_PORT".to_string(), "1".to_string()),
+        ]);
+        let mut env_layer = EnvConfigLayer::from_map(Some("MAP".to_string()), vars);
+
+        assert_eq!(env_layer.keys(), vec!["database.host".to_string()]);
+        assert_eq!(
+            env_layer.get("database.host").unwrap(),
+            Some(ConfigValue::from("localhost"))
+        );
+        assert_eq!(env_layer.get("port").unwrap(), None);
+
+        env_layer.set_source(Arc::new(MapEnv::new().with("MAP_PORT", "80")));
+        assert_eq!(env_layer.keys(), vec!["port".to_string()]);
+        assert_eq!(env_layer.get("database.host").unwrap(), None);
+        assert_eq!(
+            env_layer.get("port").unwrap(),
+            Some(ConfigValue::Integer(80))
+        );
+    }
+
+    #[test]
+    fn test_list_separator_splits_values() {
+        let env = MapEnv::new()
+            .with("LIST_HOSTS", "a.com, b.com")
+            .with("LIST_PORTS", "80;443")
+            .with("LIST_SINGLE", "only");
+        let mut env_layer = EnvConfigLayer::with_source(Some("LIST".to_string()), false, env);
+
+        assert_eq!(
+            env_layer.get("hosts").unwrap(),
+            Some(ConfigValue::from("a.com, b.com"))
+        );
+
+        env_layer.set_list_separator(Some(",".to_string()));
+        assert_eq!(
+            env_layer.get("hosts").unwrap(),
+            Some(ConfigValue::Array(vec![
+                ConfigValue::from("a.com"),
+                ConfigValue::from("b.com"),
+            ]))
+        );
+        assert_eq!(
+            env_layer.get("single").unwrap(),
+            Some(ConfigValue::Array(vec![ConfigValue::from("only")]))
+        );
+
+        env_layer.set_list_separator(Some(";".to_string()));
+        assert_eq!(
+            env_layer.get("ports").unwrap(),
+            Some(ConfigValue::Array(vec![
+                ConfigValue::Integer(80),
+                ConfigValue::Integer(443),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_dotenv() {
+        let content = r#"
+# database settings
+APP_DATABASE_HOST=localhost
+export APP_DATABASE_PORT = 5432
+APP_GREETING="hello\n\"world\"" # trailing comment
+APP_PATTERN='a\b#c'
+APP_NAME=spice # comment
+APP_EMPTY=
+"#;
+        let vars = parse_dotenv(content, ".env").unwrap();
+        assert_eq!(vars.len(), 6);
+        assert_eq!(vars["APP_DATABASE_HOST"], "localhost");
+        assert_eq!(vars["APP_DATABASE_PORT"], "5432");
+        assert_eq!(vars["APP_GREETING"], "hello\n\"world\"");
+        assert_eq!(vars["APP_PATTERN"], "a\\b#c");
+        assert_eq!(vars["APP_NAME"], "spice");
+        assert_eq!(vars["APP_EMPTY"], "");
+
+        let error = parse_dotenv("A=1\nNOT AN ASSIGNMENT\n", ".env").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error in .env: line 2: expected KEY=value"
+        );
+        assert!(parse_dotenv("A=\"open\n", ".env").is_err());
+        assert!(parse_dotenv("A='x' y\n", ".env").is_err());
+        assert!(parse_dotenv("BAD KEY=1\n", ".env").is_err());
+    }
+
+    #[test]
+    fn test_scoped_prefixes() {
+        let env = Arc::new(
+            MapEnv::new()
+                .with("DB_HOST", "db.internal")
+                .with("DB_PORT", "5432")
+                .with("CACHE_TTL", "60")
+                .with("HOST", "ignored"),
+        );
+        let mut env_layer = EnvConfigLayer::scoped("database", "DB_");
+        env_layer.set_source(env.clone());
+
+        assert_eq!(
+            env_layer.get("database.host").unwrap(),
+            Some(ConfigValue::from("db.internal"))
+        );
+        assert_eq!(
+            env_layer.get("database.port").unwrap(),
+            Some(ConfigValue::Integer(5432))
+        );
+        assert_eq!(env_layer.get("host").unwrap(), None);
+        assert_eq!(env_layer.get("database").unwrap(), None);
+        assert_eq!(env_layer.get("cache.ttl").unwrap(), None);
+
+        env_layer.bind_prefix("cache", "CACHE");
+        assert_eq!(
+            env_layer.get("cache.ttl").unwrap(),
+            Some(ConfigValue::Integer(60))
+        );
+        let mut keys = env_layer.keys();
+        keys.sort();
+        assert_eq!(keys, vec!["cache.ttl", "database.host", "database.port"]);
+
+        // A scope on an unscoped layer is checked before its global prefix
+        let mut env_layer = EnvConfigLayer::with_source(None, false, MapEnv::new());
+        env_layer.set_source(env);
+        env_layer.bind_prefix("database", "DB");
+        assert_eq!(
+            env_layer.get("database.host").unwrap(),
+            Some(ConfigValue::from("db.internal"))
+        );
+        assert_eq!(
+            env_layer.get("host").unwrap(),
+            Some(ConfigValue::from("ignored"))
+        );
     }
 
     #[test]
@@ -393,11 +1120,12 @@ mod tests {
 
     #[test]
     fn test_automatic_mode_cache() {
-        // Set some test environment variables
-        env::set_var("AUTO_TEST_VAR1", "value1");
-        env::set_var("AUTO_TEST_VAR2", "42");
+        let source = MapEnv::new()
+            .with("AUTO_TEST_VAR1", "value1")
+            .with("AUTO_TEST_VAR2", "42")
+            .with("OTHER_TEST_VAR3", "ignored");
 
-        let env_layer = EnvConfigLayer::new(Some("AUTO".to_string()), true);
+        let env_layer = EnvConfigLayer::with_source(Some("AUTO".to_string()), true, source);
 
         // Should find cached values
         let result1 = env_layer.get("test.var1").unwrap();
@@ -410,22 +1138,17 @@ mod tests {
         let keys = env_layer.keys();
         assert!(keys.contains(&"test.var1".to_string()));
         assert!(keys.contains(&"test.var2".to_string()));
-
-        // Clean up
-        env::remove_var("AUTO_TEST_VAR1");
-        env::remove_var("AUTO_TEST_VAR2");
+        assert_eq!(keys.len(), 2);
     }
 
     #[test]
     fn test_refresh_cache() {
-        let mut env_layer = EnvConfigLayer::new(Some("REFRESH".to_string()), false);
+        let source = MapEnv::new().with("REFRESH_TEST_KEY", "test_value");
+        let mut env_layer = EnvConfigLayer::with_source(Some("REFRESH".to_string()), false, source);
 
         // Initially no cached vars
         assert_eq!(env_layer.keys().len(), 0);
 
-        // Set environment variable
-        env::set_var("REFRESH_TEST_KEY", "test_value");
-
         // Enable automatic mode and refresh cache
         env_layer.automatic = true;
         env_layer.refresh_cache();
@@ -436,9 +1159,6 @@ mod tests {
 
         let result = env_layer.get("test.key").unwrap();
         assert_eq!(result, Some(ConfigValue::String("test_value".to_string())));
-
-        // Clean up
-        env::remove_var("REFRESH_TEST_KEY");
     }
 
     #[test]
@@ -460,15 +1180,11 @@ mod tests {
     #[test]
     fn test_nested_key_handling() {
         // Test deeply nested keys
-        env::set_var("NESTED_A_B_C_D", "deep_value");
-
-        let env_layer = EnvConfigLayer::new(Some("NESTED".to_string()), true);
+        let source = MapEnv::new().with("NESTED_A_B_C_D", "deep_value");
+        let env_layer = EnvConfigLayer::with_source(Some("NESTED".to_string()), true, source);
 
         let result = env_layer.get("a.b.c.d").unwrap();
         assert_eq!(result, Some(ConfigValue::String("deep_value".to_string())));
-
-        // Clean up
-        env::remove_var("NESTED_A_B_C_D");
     }
 
     #[test]
@@ -490,11 +1206,11 @@ mod tests {
 
     #[test]
     fn test_no_prefix_automatic_mode() {
-        // Set some environment variables
-        env::set_var("NO_PREFIX_TEST1", "value1");
-        env::set_var("NO_PREFIX_TEST2", "value2");
+        let source = MapEnv::new()
+            .with("NO_PREFIX_TEST1", "value1")
+            .with("NO_PREFIX_TEST2", "value2");
 
-        let env_layer = EnvConfigLayer::new(None, true);
+        let env_layer = EnvConfigLayer::with_source(None, true, source);
 
         // Should find variables without prefix filtering
         let result1 = env_layer.get("no.prefix.test1").unwrap();
@@ -502,9 +1218,6 @@ mod tests {
 
         let result2 = env_layer.get("no.prefix.test2").unwrap();
         assert_eq!(result2, Some(ConfigValue::String("value2".to_string())));
-
-        // Clean up
-        env::remove_var("NO_PREFIX_TEST1");
-        env::remove_var("NO_PREFIX_TEST2");
+        assert_eq!(env_layer.keys().len(), 2);
     }
 }
@@ -6,7 +6,7 @@ pub type ConfigResult<T> = Result<T, ConfigError>;
 /// Comprehensive error types for configuration operations.
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
-    /// IO operation failed
+    /// IO operation failed; the underlying `std::io::Error` is returned by `source()`
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -21,6 +21,10 @@ pub enum ConfigError {
     #[error("Key not found: {key}")]
     KeyNotFound { key: String },
 
+    /// One or more required configuration keys are not set
+    #[error("Missing required keys: {}", keys.join(", "))]
+    MissingKeys { keys: Vec<String> },
+
     /// Type conversion failed
     #[error("Type conversion error: cannot convert {from} to {to}")]
     TypeConversion { from: String, to: String },
@@ -48,6 +52,71 @@ pub enum ConfigError {
     /// Unsupported operation
     #[error("Unsupported operation: {0}")]
     UnsupportedOperation(String),
+
+    /// Configuration values violate a schema; each violation names its key
+    #[error("Validation failed: {}", violations.join("; "))]
+    Validation { violations: Vec<String> },
+
+    /// One or more configuration files failed to load; each failure names its file
+    #[error("Failed to load {} configuration file(s): {}", failures.len(), describe_file_failures(failures))]
+    FileErrors {
+        failures: Vec<(std::path::PathBuf, ConfigError)>,
+    },
+}
+
+/// Joins file failures into `path: error` entries for display.
+fn describe_file_failures(failures: &[(std::path::PathBuf, ConfigError)]) -> String {
+    failures
+        .iter()
+        .map(|(path, error)| format!("{}: {}", path.display(), error))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Cloning an `Io` error rebuilds the `std::io::Error` from its OS error code, or from its
+/// kind and message, since `std::io::Error` is not `Clone`.
+impl Clone for ConfigError {
+    fn clone(&self) -> Self {
+        match self {
+            ConfigError::Io(err) => ConfigError::Io(match err.raw_os_error() {
+                Some(code) => std::io::Error::from_raw_os_error(code),
+                None => std::io::Error::new(err.kind(), err.to_string()),
+            }),
+            ConfigError::Parse {
+                source_name,
+                message,
+            } => ConfigError::Parse {
+                source_name: source_name.clone(),
+                message: message.clone(),
+            },
+            ConfigError::KeyNotFound { key } => ConfigError::KeyNotFound { key: key.clone() },
+            ConfigError::MissingKeys { keys } => ConfigError::MissingKeys { keys: keys.clone() },
+            ConfigError::TypeConversion { from, to } => ConfigError::TypeConversion {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            ConfigError::UnsupportedFormat => ConfigError::UnsupportedFormat,
+            ConfigError::FileWatch(message) => ConfigError::FileWatch(message.clone()),
+            ConfigError::Serialization(message) => ConfigError::Serialization(message.clone()),
+            ConfigError::Deserialization(message) => ConfigError::Deserialization(message.clone()),
+            ConfigError::InvalidValue(message) => ConfigError::InvalidValue(message.clone()),
+            ConfigError::UnsupportedOperation(message) => {
+                ConfigError::UnsupportedOperation(message.clone())
+            }
+            ConfigError::Validation { violations } => ConfigError::Validation {
+                violations: violations.clone(),
+            },
+            ConfigError::FileErrors { failures } => ConfigError::FileErrors {
+                failures: failures.clone(),
+            },
+        }
+    }
+}
+
+impl From<crate::value::ConversionError> for ConfigError {
+    fn from(err: crate::value::ConversionError) -> Self {
+        ConfigError::type_conversion(err.from_type, err.to_type)
+    }
 }
 
 impl From<serde_json::Error> for ConfigError {
@@ -78,6 +147,11 @@ impl ConfigError {
         Self::KeyNotFound { key: key.into() }
     }
 
+    /// Creates a new missing keys error listing every absent required key.
+    pub fn missing_keys(keys: Vec<String>) -> Self {
+        Self::MissingKeys { keys }
+    }
+
     /// Creates a new file watch error.
     pub fn file_watch(message: impl Into<String>) -> Self {
         Self::FileWatch(message.into())
@@ -103,6 +177,16 @@ impl ConfigError {
         Self::UnsupportedOperation(message.into())
     }
 
+    /// Creates a new validation error from a list of violations.
+    pub fn validation(violations: Vec<String>) -> Self {
+        Self::Validation { violations }
+    }
+
+    /// Creates a new error aggregating the failures of several configuration files.
+    pub fn file_errors(failures: Vec<(std::path::PathBuf, ConfigError)>) -> Self {
+        Self::FileErrors { failures }
+    }
+
     /// Creates a new parse error with context (alias for parse_error).
     pub fn parse(source_name: impl Into<String>, message: impl Into<String>) -> Self {
         Self::parse_error(source_name, message)
@@ -110,7 +194,10 @@ impl ConfigError {
 
     /// Returns true if this error is related to a missing key.
     pub fn is_key_not_found(&self) -> bool {
-        matches!(self, ConfigError::KeyNotFound { .. })
+        matches!(
+            self,
+            ConfigError::KeyNotFound { .. } | ConfigError::MissingKeys { .. }
+        )
     }
 
     /// Returns true if this error is related to type conversion.
@@ -123,6 +210,16 @@ impl ConfigError {
         matches!(self, ConfigError::Parse { .. })
     }
 
+    /// Returns true if this error reports schema validation failures.
+    pub fn is_validation_error(&self) -> bool {
+        matches!(self, ConfigError::Validation { .. })
+    }
+
+    /// Returns true if this error aggregates failures of several configuration files.
+    pub fn is_file_errors(&self) -> bool {
+        matches!(self, ConfigError::FileErrors { .. })
+    }
+
     /// Returns true if this error is related to IO operations.
     pub fn is_io_error(&self) -> bool {
         matches!(self, ConfigError::Io(_))
@@ -202,6 +299,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_file_errors_display() {
+        let error = ConfigError::file_errors(vec![
+            (
+                "a.json".into(),
+                ConfigError::parse_error("a.json", "expected value"),
+            ),
+            ("b.yaml".into(), ConfigError::UnsupportedFormat),
+        ]);
+        assert!(error.is_file_errors());
+        assert_eq!(
+            error.to_string(),
+            "Failed to load 2 configuration file(s): a.json: Parse error in a.json: \
+             expected value; b.yaml: Unsupported configuration format"
+        );
+    }
+
     #[test]
     fn test_io_error_conversion() {
         let io_error = io::Error::new(io::ErrorKind::NotFound, "file not found");
@@ -209,6 +323,44 @@ mod tests {
         assert!(matches!(config_error, ConfigError::Io(_)));
     }
 
+    #[test]
+    fn test_io_error_source_chain() {
+        use std::error::Error;
+
+        let config_error: ConfigError =
+            io::Error::new(io::ErrorKind::PermissionDenied, "access denied").into();
+        let source = config_error.source().expect("IO errors expose their cause");
+        let io_error = source.downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_error.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(io_error.to_string(), "access denied");
+
+        assert!(ConfigError::parse_error("a.json", "bad").source().is_none());
+    }
+
+    #[test]
+    fn test_clone_preserves_errors() {
+        let error = ConfigError::file_errors(vec![(
+            "app.yaml".into(),
+            ConfigError::parse_error("app.yaml", "bad indent"),
+        )]);
+        assert_eq!(error.clone().to_string(), error.to_string());
+
+        let io_error: ConfigError =
+            io::Error::new(io::ErrorKind::NotFound, "config.toml missing").into();
+        let cloned = io_error.clone();
+        assert_eq!(cloned.to_string(), io_error.to_string());
+        match cloned {
+            ConfigError::Io(err) => assert_eq!(err.kind(), io::ErrorKind::NotFound),
+            other => panic!("Expected Io error, got {other:?}"),
+        }
+
+        let os_error = ConfigError::Io(io::Error::from_raw_os_error(2));
+        match os_error.clone() {
+            ConfigError::Io(err) => assert_eq!(err.raw_os_error(), Some(2)),
+            other => panic!("Expected Io error, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_error_context_preservation() {
         // Test that error context is preserved through helper methods
@@ -7,20 +7,24 @@ use crate::value::ConfigValue;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::SystemTime;
 
 /// File-based configuration layer that loads configuration from files.
+#[derive(Clone)]
 pub struct FileConfigLayer {
     /// Parsed configuration data
     data: HashMap<String, ConfigValue>,
     /// Path to the configuration file
     file_path: PathBuf,
     /// Parser for the file format
-    parser: Box<dyn ConfigParser>,
+    parser: Arc<dyn ConfigParser>,
     /// Last modification time for change detection
     last_modified: Option<SystemTime>,
     /// Source name for error reporting
     source_name: String,
+    /// File whose includes loaded this layer, itself included, when includes were followed
+    include_root: Option<PathBuf>,
 }
 
 impl std::fmt::Debug for FileConfigLayer {
@@ -31,6 +35,7 @@ impl std::fmt::Debug for FileConfigLayer {
             .field("parser", &self.parser.name())
             .field("last_modified", &self.last_modified)
             .field("source_name", &self.source_name)
+            .field("include_root", &self.include_root)
             .finish()
     }
 }
@@ -50,7 +55,7 @@ impl FileConfigLayer {
     /// * `ConfigError::Parse` - If the file content cannot be parsed
     pub fn new<P: AsRef<Path>>(path: P) -> ConfigResult<Self> {
         let path = path.as_ref().to_path_buf();
-        let parser = Self::detect_parser(&path)?;
+        let parser = Arc::from(Self::detect_parser(&path)?);
         let source_name = path.display().to_string();
 
         let mut layer = Self {
@@ -59,6 +64,7 @@ impl FileConfigLayer {
             parser,
             last_modified: None,
             source_name,
+            include_root: None,
         };
 
         layer.load_file()?;
@@ -83,9 +89,10 @@ impl FileConfigLayer {
         let mut layer = Self {
             data: HashMap::new(),
             file_path: path,
-            parser,
+            parser: Arc::from(parser),
             last_modified: None,
             source_name,
+            include_root: None,
         };
 
         layer.load_file()?;
@@ -181,11 +188,22 @@ impl FileConfigLayer {
     pub fn parser(&self) -> &dyn ConfigParser {
         self.parser.as_ref()
     }
+
+    /// Returns the file whose includes loaded this layer, if it was loaded while following
+    /// includes. For the including file itself this is its own path.
+    pub fn include_root(&self) -> Option<&Path> {
+        self.include_root.as_deref()
+    }
+
+    /// Records the file whose includes loaded this layer.
+    pub(crate) fn set_include_root(&mut self, root: &Path) {
+        self.include_root = Some(root.to_path_buf());
+    }
 }
 
 impl FileConfigLayer {
     /// Helper function to set nested values recursively.
-    fn set_nested_value(
+    pub(crate) fn set_nested_value(
         data: &mut HashMap<String, ConfigValue>,
         keys: &[&str],
         value: ConfigValue,
@@ -220,6 +238,21 @@ impl FileConfigLayer {
             }
         }
     }
+
+    /// Helper method to remove a nested value using a key path.
+    /// Returns true if a value was removed.
+    fn remove_nested_value(data: &mut HashMap<String, ConfigValue>, keys: &[&str]) -> bool {
+        match keys {
+            [] => false,
+            [key] => data.remove(*key).is_some(),
+            [key, rest @ ..] => match data.get_mut(*key) {
+                Some(ConfigValue::Object(nested_obj)) => {
+                    Self::remove_nested_value(nested_obj, rest)
+                }
+                _ => false,
+            },
+        }
+    }
 }
 
 impl ConfigLayer for FileConfigLayer {
@@ -251,6 +284,10 @@ impl ConfigLayer for FileConfigLayer {
         Ok(None)
     }
 
+    fn get_literal(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
+        Ok(self.data.get(key).cloned())
+    }
+
     fn set(&mut self, key: &str, value: ConfigValue) -> ConfigResult<()> {
         // File layers are typically read-only, but we can support in-memory modifications
         // Handle nested key setting with dot notation
@@ -271,6 +308,12 @@ impl ConfigLayer for FileConfigLayer {
         Ok(())
     }
 
+    fn unset(&mut self, key: &str) -> ConfigResult<bool> {
+        // Only the in-memory data is modified; the file on disk is left untouched
+        let keys: Vec<&str> = key.split('.').collect();
+        Ok(Self::remove_nested_value(&mut self.data, &keys))
+    }
+
     fn keys(&self) -> Vec<String> {
         // Return all keys including nested ones with dot notation
         let mut all_keys = Vec::new();
@@ -294,10 +337,18 @@ impl ConfigLayer for FileConfigLayer {
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self
     }
+
+    fn clone_box(&self) -> Box<dyn ConfigLayer> {
+        Box::new(self.clone())
+    }
 }
 
 /// Recursively collects all keys from a nested configuration structure.
-fn collect_keys(data: &HashMap<String, ConfigValue>, prefix: String, keys: &mut Vec<String>) {
+pub(crate) fn collect_keys(
+    data: &HashMap<String, ConfigValue>,
+    prefix: String,
+    keys: &mut Vec<String>,
+) {
     for (key, value) in data {
         let full_key = if prefix.is_empty() {
             key.clone()
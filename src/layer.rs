@@ -1,6 +1,6 @@
 //! Configuration layer abstractions and priority management.
 
-use crate::error::ConfigResult;
+use crate::error::{ConfigError, ConfigResult};
 use crate::value::ConfigValue;
 
 /// Trait for configuration layers that provide key-value access.
@@ -8,9 +8,25 @@ pub trait ConfigLayer: Send + Sync {
     /// Gets a configuration value by key.
     fn get(&self, key: &str) -> ConfigResult<Option<ConfigValue>>;
 
+    /// Gets the value stored under exactly `key`, without treating it as a nested path.
+    /// Layers whose `get` resolves dotted paths override this to look up the literal key.
+    fn get_literal(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
+        self.get(key)
+    }
+
     /// Sets a configuration value by key.
     fn set(&mut self, key: &str, value: ConfigValue) -> ConfigResult<()>;
 
+    /// Removes a configuration value by key, returning whether anything was removed.
+    /// Layers are read-only for removal unless they override this method.
+    fn unset(&mut self, key: &str) -> ConfigResult<bool> {
+        let _ = key;
+        Err(ConfigError::unsupported_operation(format!(
+            "Cannot remove values from {} layer",
+            self.source_name()
+        )))
+    }
+
     /// Returns all available keys in this layer.
     fn keys(&self) -> Vec<String>;
 
@@ -20,11 +36,21 @@ pub trait ConfigLayer: Send + Sync {
     /// Returns the priority of this layer for precedence resolution.
     fn priority(&self) -> LayerPriority;
 
+    /// Returns true if this layer reads values that can change without Spice being told,
+    /// such as process environment variables. Merged values are not cached while a
+    /// volatile layer is present.
+    fn is_volatile(&self) -> bool {
+        false
+    }
+
     /// Returns a reference to the layer as Any for downcasting.
     fn as_any(&self) -> &dyn std::any::Any;
 
     /// Returns a mutable reference to the layer as Any for downcasting.
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+
+    /// Returns a boxed copy of this layer, allowing layer collections to be cloned.
+    fn clone_box(&self) -> Box<dyn ConfigLayer>;
 }
 
 /// Priority levels for configuration layers.
@@ -46,6 +72,16 @@ pub enum LayerPriority {
 }
 
 impl LayerPriority {
+    /// Every priority level in the default precedence order, highest precedence first.
+    pub const DEFAULT_ORDER: [LayerPriority; 6] = [
+        LayerPriority::Explicit,
+        LayerPriority::Flags,
+        LayerPriority::Environment,
+        LayerPriority::ConfigFile,
+        LayerPriority::KeyValue,
+        LayerPriority::Defaults,
+    ];
+
     /// Returns a human-readable description of the priority level.
     pub fn description(&self) -> &'static str {
         match self {
@@ -59,6 +95,108 @@ impl LayerPriority {
     }
 }
 
+/// Strategy used to combine values for the same key found in several layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// The value from the highest-precedence layer replaces all others - default
+    #[default]
+    Shallow,
+    /// Objects are merged recursively across layers, so sub-keys set only in lower
+    /// precedence layers are kept; arrays are combined according to the
+    /// `ArrayMergeStrategy`, and other values are replaced by the highest-precedence layer
+    Deep,
+}
+
+/// Strategy used to combine arrays for the same key found in several layers.
+///
+/// The array strategy applies wherever two arrays meet during a merge. With
+/// `MergeStrategy::Deep` that includes arrays nested inside objects that are being
+/// merged; with `MergeStrategy::Shallow` objects are replaced whole, so only arrays
+/// stored directly under the requested key are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayMergeStrategy {
+    /// The array from the highest-precedence layer replaces all others - default
+    #[default]
+    Replace,
+    /// Elements from higher-precedence layers are appended after lower ones
+    Append,
+    /// Elements from higher-precedence layers are placed before lower ones
+    Prepend,
+    /// Arrays are merged element-wise by index, higher precedence winning at each index;
+    /// elements beyond the end of the shorter array are kept
+    ByIndex,
+}
+
+/// A layer registered under an explicit name, which it reports as its `source_name`.
+/// Everything else, including downcasting, is delegated to the wrapped layer.
+pub(crate) struct NamedLayer {
+    name: String,
+    inner: Box<dyn ConfigLayer>,
+}
+
+impl NamedLayer {
+    /// Gives `layer` the name `name`, wrapping it only if its own source name differs.
+    pub(crate) fn wrap(name: &str, layer: Box<dyn ConfigLayer>) -> Box<dyn ConfigLayer> {
+        if layer.source_name() == name {
+            layer
+        } else {
+            Box::new(Self {
+                name: name.to_string(),
+                inner: layer,
+            })
+        }
+    }
+}
+
+impl ConfigLayer for NamedLayer {
+    fn get(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
+        self.inner.get(key)
+    }
+
+    fn get_literal(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
+        self.inner.get_literal(key)
+    }
+
+    fn set(&mut self, key: &str, value: ConfigValue) -> ConfigResult<()> {
+        self.inner.set(key, value)
+    }
+
+    fn unset(&mut self, key: &str) -> ConfigResult<bool> {
+        self.inner.unset(key)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.inner.keys()
+    }
+
+    fn source_name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> LayerPriority {
+        self.inner.priority()
+    }
+
+    fn is_volatile(&self) -> bool {
+        self.inner.is_volatile()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self.inner.as_any_mut()
+    }
+
+    fn clone_box(&self) -> Box<dyn ConfigLayer> {
+        Box::new(Self {
+            name: self.name.clone(),
+            inner: self.inner.clone_box(),
+        })
+    }
+}
+
 /// Layer management utilities for sorting and merging configuration layers.
 pub mod utils {
     use super::*;
@@ -77,6 +215,7 @@ pub mod utils {
     /// use std::collections::HashMap;
     ///
     /// // Mock layer for example
+    /// #[derive(Clone)]
     /// struct MockLayer { priority: LayerPriority }
     /// impl ConfigLayer for MockLayer {
     ///     fn get(&self, _key: &str) -> ConfigResult<Option<ConfigValue>> { Ok(None) }
@@ -86,6 +225,7 @@ pub mod utils {
     ///     fn priority(&self) -> LayerPriority { self.priority }
     ///     fn as_any(&self) -> &dyn std::any::Any { self }
     ///     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    ///     fn clone_box(&self) -> Box<dyn ConfigLayer> { Box::new(self.clone()) }
     /// }
     ///
     /// let mut layers: Vec<Box<dyn ConfigLayer>> = vec![
@@ -97,7 +237,46 @@ pub mod utils {
     /// assert_eq!(layers[0].priority(), LayerPriority::Explicit);
     /// ```
     pub fn sort_layers_by_priority(layers: &mut [Box<dyn ConfigLayer>]) {
-        layers.sort_by_key(|a| a.priority());
+        sort_layers_by_order(layers, &LayerPriority::DEFAULT_ORDER);
+    }
+
+    /// Sorts configuration layers by the position of their priority in `order`
+    /// (earlier entries first). Priorities missing from `order` sort after those listed,
+    /// in their default order. Layers of equal priority keep their relative order.
+    ///
+    /// # Arguments
+    /// * `layers` - A mutable slice of configuration layers to sort
+    /// * `order` - Priority levels from highest to lowest precedence
+    pub fn sort_layers_by_order(layers: &mut [Box<dyn ConfigLayer>], order: &[LayerPriority]) {
+        layers.sort_by_key(|layer| priority_rank(order, layer.priority()));
+    }
+
+    /// Returns the rank of `priority` within `order`; lower ranks take precedence.
+    /// Priorities missing from `order` rank after every listed priority.
+    ///
+    /// # Arguments
+    /// * `order` - Priority levels from highest to lowest precedence
+    /// * `priority` - The priority level to rank
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::layer::{utils::priority_rank, LayerPriority};
+    ///
+    /// let order = [LayerPriority::Environment, LayerPriority::Flags];
+    /// assert!(
+    ///     priority_rank(&order, LayerPriority::Environment)
+    ///         < priority_rank(&order, LayerPriority::Flags)
+    /// );
+    /// assert!(
+    ///     priority_rank(&order, LayerPriority::Flags)
+    ///         < priority_rank(&order, LayerPriority::Explicit)
+    /// );
+    /// ```
+    pub fn priority_rank(order: &[LayerPriority], priority: LayerPriority) -> usize {
+        order
+            .iter()
+            .position(|&p| p == priority)
+            .unwrap_or(order.len() + priority as usize)
     }
 
     /// Merges configuration values from multiple layers according to precedence.
@@ -118,6 +297,7 @@ pub mod utils {
     /// use std::collections::HashMap;
     ///
     /// // Mock layer for example
+    /// #[derive(Clone)]
     /// struct MockLayer { data: HashMap<String, ConfigValue> }
     /// impl ConfigLayer for MockLayer {
     ///     fn get(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
@@ -129,6 +309,7 @@ pub mod utils {
     ///     fn priority(&self) -> LayerPriority { LayerPriority::ConfigFile }
     ///     fn as_any(&self) -> &dyn std::any::Any { self }
     ///     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    ///     fn clone_box(&self) -> Box<dyn ConfigLayer> { Box::new(self.clone()) }
     /// }
     ///
     /// let mut data = HashMap::new();
@@ -150,6 +331,176 @@ pub mod utils {
         Ok(None)
     }
 
+    /// Merges configuration values from multiple layers using the given strategies.
+    /// With `MergeStrategy::Shallow` and `ArrayMergeStrategy::Replace` this is equivalent
+    /// to `merge_value_from_layers`. With `MergeStrategy::Deep`, objects found for the key
+    /// in several layers are merged recursively, with higher precedence layers winning on
+    /// conflicting sub-keys. Arrays that meet during the merge are combined according to
+    /// `array_strategy`.
+    ///
+    /// # Arguments
+    /// * `layers` - Slice of configuration layers sorted by priority (highest first)
+    /// * `key` - The configuration key to search for
+    /// * `strategy` - How objects from different layers are combined
+    /// * `array_strategy` - How arrays from different layers are combined
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<ConfigValue>>` - The merged value or None if not found
+    pub fn merge_value_from_layers_with_strategy(
+        layers: &[Box<dyn ConfigLayer>],
+        key: &str,
+        strategy: MergeStrategy,
+        array_strategy: ArrayMergeStrategy,
+    ) -> ConfigResult<Option<ConfigValue>> {
+        merge_layer_values_with(layers, strategy, array_strategy, |layer| layer.get(key))
+    }
+
+    /// Merges the values that `lookup` produces for each layer using the given strategies.
+    /// This is the building block of `merge_value_from_layers_with_strategy` for callers
+    /// that need to customize how a single layer is queried.
+    ///
+    /// # Arguments
+    /// * `layers` - Slice of configuration layers sorted by priority (highest first)
+    /// * `strategy` - How objects from different layers are combined
+    /// * `array_strategy` - How arrays from different layers are combined
+    /// * `lookup` - Function returning the value a layer provides, if any
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<ConfigValue>>` - The merged value or None if no layer provides one
+    pub fn merge_layer_values_with<F>(
+        layers: &[Box<dyn ConfigLayer>],
+        strategy: MergeStrategy,
+        array_strategy: ArrayMergeStrategy,
+        mut lookup: F,
+    ) -> ConfigResult<Option<ConfigValue>>
+    where
+        F: FnMut(&dyn ConfigLayer) -> ConfigResult<Option<ConfigValue>>,
+    {
+        if strategy == MergeStrategy::Shallow && array_strategy == ArrayMergeStrategy::Replace {
+            for layer in layers {
+                if let Some(value) = lookup(layer.as_ref())? {
+                    return Ok(Some(value));
+                }
+            }
+            return Ok(None);
+        }
+
+        // Walk from lowest to highest precedence so higher layers overlay lower ones
+        let mut merged: Option<ConfigValue> = None;
+        for layer in layers.iter().rev() {
+            if let Some(value) = lookup(layer.as_ref())? {
+                merged = Some(match merged {
+                    Some(base) => merge_values(base, value, strategy, array_strategy),
+                    None => value,
+                });
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Recursively merges `overlay` on top of `base`.
+    /// When both values are objects their keys are merged, recursing into shared keys.
+    /// In every other case, arrays included, `overlay` replaces `base`.
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::layer::utils::deep_merge;
+    /// use spicex::ConfigValue;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut base = HashMap::new();
+    /// base.insert("host".to_string(), ConfigValue::from("localhost"));
+    /// let mut overlay = HashMap::new();
+    /// overlay.insert("port".to_string(), ConfigValue::from(5432i64));
+    ///
+    /// let merged = deep_merge(ConfigValue::Object(base), ConfigValue::Object(overlay));
+    /// let obj = merged.as_object().unwrap();
+    /// assert_eq!(obj.len(), 2);
+    /// ```
+    pub fn deep_merge(base: ConfigValue, overlay: ConfigValue) -> ConfigValue {
+        merge_values(
+            base,
+            overlay,
+            MergeStrategy::Deep,
+            ArrayMergeStrategy::Replace,
+        )
+    }
+
+    /// Merges `overlay` on top of `base` using the given strategies.
+    /// Objects are merged recursively only with `MergeStrategy::Deep`, arrays are
+    /// combined according to `array_strategy`, and any other pairing is resolved in
+    /// favour of `overlay`.
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::layer::utils::merge_values;
+    /// use spicex::{config_value, ArrayMergeStrategy, MergeStrategy};
+    ///
+    /// let merged = merge_values(
+    ///     config_value!(["a", "b"]),
+    ///     config_value!(["c"]),
+    ///     MergeStrategy::Shallow,
+    ///     ArrayMergeStrategy::Append,
+    /// );
+    /// assert_eq!(merged, config_value!(["a", "b", "c"]));
+    /// ```
+    pub fn merge_values(
+        base: ConfigValue,
+        overlay: ConfigValue,
+        strategy: MergeStrategy,
+        array_strategy: ArrayMergeStrategy,
+    ) -> ConfigValue {
+        match (base, overlay) {
+            (ConfigValue::Object(mut base_obj), ConfigValue::Object(overlay_obj))
+                if strategy == MergeStrategy::Deep =>
+            {
+                for (key, value) in overlay_obj {
+                    let merged = match base_obj.remove(&key) {
+                        Some(existing) => merge_values(existing, value, strategy, array_strategy),
+                        None => value,
+                    };
+                    base_obj.insert(key, merged);
+                }
+                ConfigValue::Object(base_obj)
+            }
+            (ConfigValue::Array(base_arr), ConfigValue::Array(overlay_arr)) => ConfigValue::Array(
+                merge_arrays(base_arr, overlay_arr, strategy, array_strategy),
+            ),
+            (_, overlay) => overlay,
+        }
+    }
+
+    fn merge_arrays(
+        mut base: Vec<ConfigValue>,
+        mut overlay: Vec<ConfigValue>,
+        strategy: MergeStrategy,
+        array_strategy: ArrayMergeStrategy,
+    ) -> Vec<ConfigValue> {
+        match array_strategy {
+            ArrayMergeStrategy::Replace => overlay,
+            ArrayMergeStrategy::Append => {
+                base.append(&mut overlay);
+                base
+            }
+            ArrayMergeStrategy::Prepend => {
+                overlay.append(&mut base);
+                overlay
+            }
+            ArrayMergeStrategy::ByIndex => {
+                let mut overlay = overlay.into_iter();
+                let mut merged: Vec<ConfigValue> = base
+                    .into_iter()
+                    .map(|existing| match overlay.next() {
+                        Some(value) => merge_values(existing, value, strategy, array_strategy),
+                        None => existing,
+                    })
+                    .collect();
+                merged.extend(overlay);
+                merged
+            }
+        }
+    }
+
     /// Collects all unique keys from multiple configuration layers.
     ///
     /// # Arguments
@@ -166,6 +517,7 @@ pub mod utils {
     /// use std::collections::HashMap;
     ///
     /// // Mock layer for example
+    /// #[derive(Clone)]
     /// struct MockLayer { keys: Vec<String> }
     /// impl ConfigLayer for MockLayer {
     ///     fn get(&self, _key: &str) -> ConfigResult<Option<ConfigValue>> { Ok(None) }
@@ -175,6 +527,7 @@ pub mod utils {
     ///     fn priority(&self) -> LayerPriority { LayerPriority::ConfigFile }
     ///     fn as_any(&self) -> &dyn std::any::Any { self }
     ///     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    ///     fn clone_box(&self) -> Box<dyn ConfigLayer> { Box::new(self.clone()) }
     /// }
     ///
     /// let layers: Vec<Box<dyn ConfigLayer>> = vec![
@@ -215,6 +568,7 @@ pub mod utils {
     /// use std::collections::HashMap;
     ///
     /// // Mock layer for example
+    /// #[derive(Clone)]
     /// struct MockLayer { data: HashMap<String, ConfigValue> }
     /// impl ConfigLayer for MockLayer {
     ///     fn get(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
@@ -226,6 +580,7 @@ pub mod utils {
     ///     fn priority(&self) -> LayerPriority { LayerPriority::ConfigFile }
     ///     fn as_any(&self) -> &dyn std::any::Any { self }
     ///     fn as_any_mut(&mut self) -> &mut dyn std::any::Any { self }
+    ///     fn clone_box(&self) -> Box<dyn ConfigLayer> { Box::new(self.clone()) }
     /// }
     ///
     /// let mut data = HashMap::new();
@@ -248,14 +603,44 @@ pub mod utils {
 
         Ok(merged)
     }
+
+    /// Creates a merged view of all configuration values using the given strategies.
+    ///
+    /// # Arguments
+    /// * `layers` - Slice of configuration layers sorted by priority (highest first)
+    /// * `strategy` - How objects from different layers are combined
+    /// * `array_strategy` - How arrays from different layers are combined
+    ///
+    /// # Returns
+    /// * `ConfigResult<HashMap<String, ConfigValue>>` - Merged configuration map
+    pub fn merge_all_layers_with_strategy(
+        layers: &[Box<dyn ConfigLayer>],
+        strategy: MergeStrategy,
+        array_strategy: ArrayMergeStrategy,
+    ) -> ConfigResult<HashMap<String, ConfigValue>> {
+        let mut merged = HashMap::new();
+        let all_keys = collect_all_keys(layers);
+
+        for key in all_keys {
+            if let Some(value) =
+                merge_value_from_layers_with_strategy(layers, &key, strategy, array_strategy)?
+            {
+                merged.insert(key, value);
+            }
+        }
+
+        Ok(merged)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config_value;
     use std::collections::HashMap;
 
     // Mock implementation for testing
+    #[derive(Clone)]
     struct MockConfigLayer {
         data: HashMap<String, ConfigValue>,
         priority: LayerPriority,
@@ -306,6 +691,10 @@ mod tests {
         fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
             self
         }
+
+        fn clone_box(&self) -> Box<dyn ConfigLayer> {
+            Box::new(self.clone())
+        }
     }
 
     #[test]
@@ -341,6 +730,24 @@ mod tests {
         assert_eq!(layers[3].priority(), LayerPriority::Defaults);
     }
 
+    #[test]
+    fn test_sort_layers_by_order() {
+        let mut layers: Vec<Box<dyn ConfigLayer>> = vec![
+            Box::new(MockConfigLayer::new("defaults", LayerPriority::Defaults)),
+            Box::new(MockConfigLayer::new("flags", LayerPriority::Flags)),
+            Box::new(MockConfigLayer::new("env", LayerPriority::Environment)),
+            Box::new(MockConfigLayer::new("explicit", LayerPriority::Explicit)),
+        ];
+
+        utils::sort_layers_by_order(
+            &mut layers,
+            &[LayerPriority::Environment, LayerPriority::Flags],
+        );
+
+        let names: Vec<&str> = layers.iter().map(|layer| layer.source_name()).collect();
+        assert_eq!(names, vec!["env", "flags", "explicit", "defaults"]);
+    }
+
     #[test]
     fn test_merge_value_from_layers_precedence() {
         let layers: Vec<Box<dyn ConfigLayer>> = vec![
@@ -447,6 +854,165 @@ mod tests {
         );
     }
 
+    fn object(entries: Vec<(&str, ConfigValue)>) -> ConfigValue {
+        ConfigValue::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_deep_merge_across_layers() {
+        let layers: Vec<Box<dyn ConfigLayer>> = vec![
+            Box::new(
+                MockConfigLayer::new("file_a", LayerPriority::ConfigFile).with_value(
+                    "database",
+                    object(vec![
+                        ("host", ConfigValue::from("db.example.com")),
+                        ("pool", object(vec![("max", ConfigValue::from(20i64))])),
+                        ("replicas", ConfigValue::Array(vec![ConfigValue::from("a")])),
+                    ]),
+                ),
+            ),
+            Box::new(
+                MockConfigLayer::new("file_b", LayerPriority::ConfigFile).with_value(
+                    "database",
+                    object(vec![
+                        ("host", ConfigValue::from("localhost")),
+                        ("port", ConfigValue::from(5432i64)),
+                        ("pool", object(vec![("min", ConfigValue::from(2i64))])),
+                        (
+                            "replicas",
+                            ConfigValue::Array(vec![
+                                ConfigValue::from("b"),
+                                ConfigValue::from("c"),
+                            ]),
+                        ),
+                    ]),
+                ),
+            ),
+        ];
+
+        // Shallow keeps the whole object from the first layer
+        let shallow = utils::merge_value_from_layers_with_strategy(
+            &layers,
+            "database",
+            MergeStrategy::Shallow,
+            ArrayMergeStrategy::Replace,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(shallow.as_object().unwrap().get("port").is_none());
+
+        // Deep merges sub-keys, higher precedence wins, arrays are replaced
+        let deep = utils::merge_value_from_layers_with_strategy(
+            &layers,
+            "database",
+            MergeStrategy::Deep,
+            ArrayMergeStrategy::Replace,
+        )
+        .unwrap()
+        .unwrap();
+        let deep = deep.as_object().unwrap();
+        assert_eq!(deep.get("host"), Some(&ConfigValue::from("db.example.com")));
+        assert_eq!(deep.get("port"), Some(&ConfigValue::from(5432i64)));
+        let pool = deep.get("pool").unwrap().as_object().unwrap();
+        assert_eq!(pool.len(), 2);
+        assert_eq!(
+            deep.get("replicas"),
+            Some(&ConfigValue::Array(vec![ConfigValue::from("a")]))
+        );
+    }
+
+    #[test]
+    fn test_deep_merge_non_object_overlay_wins() {
+        let base = object(vec![("host", ConfigValue::from("localhost"))]);
+        assert_eq!(
+            utils::deep_merge(base.clone(), ConfigValue::from("flat")),
+            ConfigValue::from("flat")
+        );
+        assert_eq!(utils::deep_merge(ConfigValue::Null, base.clone()), base);
+    }
+
+    #[test]
+    fn test_array_merge_strategies() {
+        let layers: Vec<Box<dyn ConfigLayer>> = vec![
+            Box::new(
+                MockConfigLayer::new("env", LayerPriority::Environment)
+                    .with_value("hosts", config_value!(["c", "d"])),
+            ),
+            Box::new(
+                MockConfigLayer::new("defaults", LayerPriority::Defaults)
+                    .with_value("hosts", config_value!(["a", "b", "z"])),
+            ),
+        ];
+
+        let merge = |array_strategy| {
+            utils::merge_value_from_layers_with_strategy(
+                &layers,
+                "hosts",
+                MergeStrategy::Shallow,
+                array_strategy,
+            )
+            .unwrap()
+            .unwrap()
+        };
+
+        assert_eq!(
+            merge(ArrayMergeStrategy::Replace),
+            config_value!(["c", "d"])
+        );
+        assert_eq!(
+            merge(ArrayMergeStrategy::Append),
+            config_value!(["a", "b", "z", "c", "d"])
+        );
+        assert_eq!(
+            merge(ArrayMergeStrategy::Prepend),
+            config_value!(["c", "d", "a", "b", "z"])
+        );
+        assert_eq!(
+            merge(ArrayMergeStrategy::ByIndex),
+            config_value!(["c", "d", "z"])
+        );
+    }
+
+    #[test]
+    fn test_array_merge_strategy_inside_deep_merge() {
+        let base = config_value!({
+            "servers": [{ "host": "a", "port": 80 }, { "host": "b" }],
+            "tags": ["x"]
+        });
+        let overlay = config_value!({
+            "servers": [{ "port": 8080 }],
+            "tags": ["y"]
+        });
+
+        let merged = utils::merge_values(
+            base.clone(),
+            overlay.clone(),
+            MergeStrategy::Deep,
+            ArrayMergeStrategy::ByIndex,
+        );
+        assert_eq!(
+            merged,
+            config_value!({
+                "servers": [{ "host": "a", "port": 8080 }, { "host": "b" }],
+                "tags": ["y"]
+            })
+        );
+
+        // Under a shallow merge the overlay object replaces the base whole
+        let merged = utils::merge_values(
+            base,
+            overlay.clone(),
+            MergeStrategy::Shallow,
+            ArrayMergeStrategy::Append,
+        );
+        assert_eq!(merged, overlay);
+    }
+
     #[test]
     fn test_layer_precedence_resolution() {
         // Test the complete precedence chain
@@ -6,7 +6,7 @@
 //! configuration needs and formats. It supports:
 //!
 //! - Setting defaults
-//! - Reading from JSON, TOML, YAML, INI configuration files
+//! - Reading from JSON, TOML, YAML, INI and (with the `xml` feature) XML configuration files
 //! - Reading from environment variables
 //! - Reading from command line flags
 //! - Reading from remote config systems (etcd, Consul)
@@ -195,24 +195,38 @@
 //! }
 //! ```
 
+pub mod builder;
 pub mod config;
+pub mod decode;
 pub mod default_layer;
+mod deserializer;
+pub mod diagnostics;
+pub mod diff;
 pub mod env_layer;
 pub mod error;
 pub mod file_layer;
+mod interpolate;
 pub mod layer;
 pub mod parser;
+pub mod schema;
+pub mod shared;
 pub mod value;
 pub mod watcher;
 
 // Re-export main types for convenience
-pub use config::Spice;
+pub use builder::SpiceBuilder;
+pub use config::{ConfigSnapshot, FrozenConfig, MigrationFn, Spice};
 pub use default_layer::DefaultConfigLayer;
+pub use diagnostics::{Diagnostic, Severity};
+pub use diff::ConfigDiff;
 pub use env_layer::EnvConfigLayer;
 pub use error::{ConfigError, ConfigResult};
 pub use file_layer::FileConfigLayer;
-pub use layer::{ConfigLayer, LayerPriority};
+pub use layer::{ArrayMergeStrategy, ConfigLayer, LayerPriority, MergeStrategy};
+pub use schema::{ConfigSchema, ValueType};
+pub use shared::SharedSpice;
 pub use value::ConfigValue;
+pub use watcher::{ConfigChange, WatchState};
 
 #[cfg(feature = "cli")]
 pub mod cli;
@@ -220,6 +234,18 @@ pub mod cli;
 #[cfg(feature = "cli")]
 pub use cli::FlagConfigLayer;
 
+#[cfg(feature = "tokio")]
+pub use watcher::{ConfigChangeStream, WatchHandle};
+
+#[cfg(feature = "remote")]
+pub mod remote;
+
+#[cfg(feature = "remote")]
+pub use remote::RedisConfigLayer;
+
+#[cfg(feature = "xml")]
+mod xml;
+
 // Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -10,6 +10,7 @@
 //! - **YAML** - YAML Ain't Markup Language, human-readable data serialization standard
 //! - **TOML** - Tom's Obvious, Minimal Language, designed for configuration files
 //! - **INI** - Initialization file format, simple key-value pairs with sections
+//! - **XML** - Extensible Markup Language, available with the `xml` feature
 //!
 //! ## Parser Detection
 //!
@@ -162,6 +163,44 @@ pub trait ConfigParser: Send + Sync {
     /// ```
     fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String>;
 
+    /// Serializes configuration data as an update of existing content in this format.
+    ///
+    /// Formats that can keep the layout of the original, such as comments and the order
+    /// of keys, override this to do so. The default implementation ignores `original` and
+    /// calls `serialize`.
+    ///
+    /// # Arguments
+    /// * `original` - The current content of the file being rewritten
+    /// * `data` - The configuration data to write
+    ///
+    /// # Returns
+    /// * `ConfigResult<String>` - The updated content
+    ///
+    /// # Errors
+    /// * `ConfigError::Serialization` - If the data cannot be represented in this format
+    ///
+    /// # Example
+    /// ```rust
+    /// use spicex::parser::{IniParser, ConfigParser};
+    /// use spicex::ConfigValue;
+    ///
+    /// let parser = IniParser;
+    /// let original = "; Listen port\nport = 8080\n";
+    /// let mut data = parser.parse(original).unwrap();
+    /// data.insert("port".to_string(), ConfigValue::from(9090i64));
+    ///
+    /// let updated = parser.serialize_update(original, &data).unwrap();
+    /// assert_eq!(updated, "; Listen port\nport = 9090\n");
+    /// ```
+    fn serialize_update(
+        &self,
+        original: &str,
+        data: &HashMap<String, ConfigValue>,
+    ) -> ConfigResult<String> {
+        let _ = original;
+        self.serialize(data)
+    }
+
     /// Returns the file extensions supported by this parser.
     ///
     /// This method returns a slice of file extensions (without the dot) that
@@ -217,6 +256,7 @@ pub trait ConfigParser: Send + Sync {
 /// - `yaml`, `yml` - YAML parser
 /// - `toml` - TOML parser
 /// - `ini` - INI parser
+/// - `xml` - XML parser (requires the `xml` feature)
 ///
 /// # Example
 /// ```rust
@@ -236,12 +276,22 @@ pub trait ConfigParser: Send + Sync {
 /// let result = detect_parser_by_extension("unknown");
 /// assert!(result.is_err());
 /// ```
+/// File extensions that `detect_parser_by_extension` recognizes, in search order.
+#[cfg(not(feature = "xml"))]
+pub(crate) const SUPPORTED_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "toml", "ini"];
+
+/// File extensions that `detect_parser_by_extension` recognizes, in search order.
+#[cfg(feature = "xml")]
+pub(crate) const SUPPORTED_EXTENSIONS: &[&str] = &["json", "yaml", "yml", "toml", "ini", "xml"];
+
 pub fn detect_parser_by_extension(extension: &str) -> ConfigResult<Box<dyn ConfigParser>> {
     match extension.to_lowercase().as_str() {
         "json" => Ok(Box::new(JsonParser)),
         "yaml" | "yml" => Ok(Box::new(YamlParser)),
         "toml" => Ok(Box::new(TomlParser)),
         "ini" => Ok(Box::new(IniParser)),
+        #[cfg(feature = "xml")]
+        "xml" => Ok(Box::new(XmlParser)),
         _ => Err(ConfigError::UnsupportedFormat),
     }
 }
@@ -296,14 +346,46 @@ impl ConfigParser for JsonParser {
     }
 
     fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
-        // Convert ConfigValue map to serde_json::Value for serialization
-        let json_map: serde_json::Map<String, serde_json::Value> = data
-            .iter()
-            .map(|(k, v)| (k.clone(), config_value_to_json(v)))
-            .collect();
+        serde_json::to_string_pretty(&config_map_to_json(data))
+            .map_err(|e| ConfigError::Serialization(e.to_string()))
+    }
 
-        let json_value = serde_json::Value::Object(json_map);
-        serde_json::to_string_pretty(&json_value)
+    fn supported_extensions(&self) -> &[&str] {
+        &["json"]
+    }
+
+    fn name(&self) -> &str {
+        "JSON"
+    }
+}
+
+/// JSON configuration parser that serializes compactly on a single line.
+///
+/// Parsing is identical to `JsonParser`; only the serialized output differs. Use it with
+/// `Spice::write_config_with_parser` when configuration is sent or stored rather than
+/// edited by hand.
+///
+/// # Example
+/// ```rust
+/// use spicex::parser::{CompactJsonParser, ConfigParser};
+/// use spicex::ConfigValue;
+/// use std::collections::HashMap;
+///
+/// let mut data = HashMap::new();
+/// data.insert("port".to_string(), ConfigValue::from(8080i64));
+///
+/// let compact = CompactJsonParser.serialize(&data).unwrap();
+/// assert_eq!(compact, r#"{"port":8080}"#);
+/// ```
+pub struct CompactJsonParser;
+
+impl ConfigParser for CompactJsonParser {
+    fn parse(&self, content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
+        JsonParser.parse(content)
+    }
+
+    fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
+        serde_json::to_string(&config_map_to_json(data))
             .map_err(|e| ConfigError::Serialization(e.to_string()))
     }
 
@@ -316,6 +398,16 @@ impl ConfigParser for JsonParser {
     }
 }
 
+/// Converts a ConfigValue map to a JSON object with sorted keys.
+fn config_map_to_json(data: &HashMap<String, ConfigValue>) -> serde_json::Value {
+    serde_json::Value::Object(
+        sorted_entries(data)
+            .into_iter()
+            .map(|(k, v)| (k.clone(), config_value_to_json(v)))
+            .collect(),
+    )
+}
+
 /// YAML configuration parser.
 ///
 /// This parser handles YAML Ain't Markup Language (YAML) format configuration files.
@@ -338,13 +430,18 @@ impl ConfigParser for JsonParser {
 /// - Anchors and aliases
 /// - Tagged values
 ///
+/// # Multiple Documents
+/// When a file holds several documents separated by `---`, their top-level mappings are
+/// merged in order, so a key in a later document replaces the same key from an earlier
+/// one. Empty documents are skipped, and every other document must be a mapping.
+///
 /// # Example
 /// ```rust
 /// use spicex::parser::{YamlParser, ConfigParser};
 ///
 /// let parser = YamlParser;
 /// let yaml_content = r#"
-/// # Database configuration
+/// ## Database configuration
 /// database:
 ///   host: localhost
 ///   port: 5432
@@ -353,13 +450,13 @@ impl ConfigParser for JsonParser {
 ///     username: admin
 ///     password: secret
 ///
-/// # Feature flags
+/// ## Feature flags
 /// features:
 ///   - auth
 ///   - logging
 ///   - metrics
 ///
-/// # Timeout in seconds
+/// ## Timeout in seconds
 /// timeout: 30.5
 /// "#;
 ///
@@ -371,16 +468,39 @@ pub struct YamlParser;
 
 impl ConfigParser for YamlParser {
     fn parse(&self, content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
-        let value: serde_yaml::Value = serde_yaml::from_str(content)
-            .map_err(|e| ConfigError::parse_error("YAML", e.to_string()))?;
+        use serde::Deserialize;
+
+        let mut documents = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(content) {
+            let value = serde_yaml::Value::deserialize(document)
+                .map_err(|e| ConfigError::parse_error("YAML", e.to_string()))?;
+            documents.push(value);
+        }
+
+        if documents.len() <= 1 {
+            return convert_yaml_value(documents.pop().unwrap_or(serde_yaml::Value::Null));
+        }
 
-        convert_yaml_value(value)
+        let mut result = HashMap::new();
+        for (index, value) in documents.into_iter().enumerate() {
+            if value.is_null() {
+                continue;
+            }
+            if !value.is_mapping() {
+                return Err(ConfigError::parse_error(
+                    "YAML",
+                    format!("Document {} must be a mapping/object", index + 1),
+                ));
+            }
+            result.extend(convert_yaml_value(value)?);
+        }
+        Ok(result)
     }
 
     fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
         // Convert ConfigValue map to serde_yaml::Value for serialization
         let mut yaml_map = serde_yaml::Mapping::new();
-        for (k, v) in data {
+        for (k, v) in sorted_entries(data) {
             yaml_map.insert(
                 serde_yaml::Value::String(k.clone()),
                 config_value_to_yaml(v),
@@ -429,7 +549,7 @@ impl ConfigParser for YamlParser {
 ///
 /// let parser = TomlParser;
 /// let toml_content = r#"
-/// # Application configuration
+/// ## Application configuration
 /// title = "My Application"
 /// debug = true
 /// timeout = 30.5
@@ -470,7 +590,7 @@ impl ConfigParser for TomlParser {
     fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
         // Convert ConfigValue map to toml::Value for serialization
         let mut toml_table = toml::map::Map::new();
-        for (k, v) in data {
+        for (k, v) in sorted_entries(data) {
             toml_table.insert(k.clone(), config_value_to_toml(v));
         }
 
@@ -499,12 +619,15 @@ impl ConfigParser for TomlParser {
 /// - Sections with `[section]` headers
 /// - Comments starting with `;` or `#`
 /// - String, integer, float, and boolean values
+/// - Quoted strings (`"..."` or `'...'`) with `\n`, `\t`, `\\` and `\"` escapes
 /// - Global properties (outside of sections)
 /// - Case-insensitive section and key names
+/// - Arrays from keys repeated within a section (`server = a`, `server = b`)
+/// - Optionally, arrays from separated values (`hosts = a.com, b.com`), see
+///   `CustomIniParser::split_values_on`
 ///
 /// # Format Limitations
 /// - No nested sections (flat structure only)
-/// - No arrays (though values can be comma-separated strings)
 /// - No complex data types
 /// - Limited escaping support
 ///
@@ -525,7 +648,7 @@ impl ConfigParser for TomlParser {
 /// debug = true
 /// timeout = 30
 ///
-/// # Database section
+/// ## Database section
 /// [database]
 /// host = localhost
 /// port = 5432
@@ -545,11 +668,21 @@ pub struct IniParser;
 
 impl ConfigParser for IniParser {
     fn parse(&self, content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
-        parse_ini_content(content)
+        CustomIniParser::new().parse(content)
     }
 
     fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
-        serialize_ini_data(data)
+        CustomIniParser::new().serialize(data)
+    }
+
+    /// Rewrites `original` with the values in `data`, keeping its comments and layout,
+    /// as `CustomIniParser::serialize_update` does.
+    fn serialize_update(
+        &self,
+        original: &str,
+        data: &HashMap<String, ConfigValue>,
+    ) -> ConfigResult<String> {
+        CustomIniParser::new().serialize_update(original, data)
     }
 
     fn supported_extensions(&self) -> &[&str] {
@@ -561,7 +694,300 @@ impl ConfigParser for IniParser {
     }
 }
 
-fn parse_ini_content(content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
+/// INI configuration parser with non-default options.
+///
+/// Reads and writes the same format as `IniParser`, which behaves like
+/// `CustomIniParser::new()`. The builder methods change how repeated keys, separated
+/// values and keys outside of any section are handled.
+///
+/// # Example
+/// ```rust
+/// use spicex::parser::{ConfigParser, CustomIniParser};
+/// use spicex::ConfigValue;
+///
+/// let parser = CustomIniParser::new()
+///     .split_values_on(',')
+///     .with_default_section("DEFAULT");
+/// let parsed = parser.parse("hosts = a.com, b.com\n").unwrap();
+///
+/// let defaults = parsed["DEFAULT"].as_object().unwrap();
+/// assert_eq!(
+///     defaults["hosts"],
+///     ConfigValue::Array(vec![ConfigValue::from("a.com"), ConfigValue::from("b.com")])
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CustomIniParser {
+    repeated_keys_as_array: bool,
+    value_separator: Option<char>,
+    default_section: Option<String>,
+}
+
+impl Default for CustomIniParser {
+    fn default() -> Self {
+        Self {
+            repeated_keys_as_array: true,
+            value_separator: None,
+            default_section: None,
+        }
+    }
+}
+
+impl CustomIniParser {
+    /// Creates an INI parser with the same behavior as `IniParser`, which collects
+    /// repeated keys into arrays.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether a key repeated within a section becomes an array of all its values.
+    ///
+    /// This is enabled by default. When disabled, the last occurrence of a key wins, and
+    /// arrays are still written as one line per element on serialization.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to collect repeated keys into arrays
+    ///
+    /// # Example
+    /// ```rust
+    /// use spicex::parser::{ConfigParser, CustomIniParser, IniParser};
+    /// use spicex::ConfigValue;
+    ///
+    /// let content = "server = a\nserver = b\n";
+    ///
+    /// let parsed = IniParser.parse(content).unwrap();
+    /// assert_eq!(
+    ///     parsed["server"],
+    ///     ConfigValue::Array(vec![ConfigValue::from("a"), ConfigValue::from("b")])
+    /// );
+    ///
+    /// let parsed = CustomIniParser::new()
+    ///     .with_repeated_keys_as_array(false)
+    ///     .parse(content)
+    ///     .unwrap();
+    /// assert_eq!(parsed["server"], ConfigValue::from("b"));
+    /// ```
+    pub fn with_repeated_keys_as_array(mut self, enabled: bool) -> Self {
+        self.repeated_keys_as_array = enabled;
+        self
+    }
+
+    /// Splits values containing `separator` into arrays of trimmed strings.
+    ///
+    /// Off by default, in which case such values stay single strings. A quoted value is
+    /// never split. On serialization, arrays are joined back with the separator, and
+    /// strings containing it are quoted. An array with an element containing the separator
+    /// is written as a repeated key instead.
+    ///
+    /// # Arguments
+    /// * `separator` - The character separating list elements, commonly `,`
+    ///
+    /// # Example
+    /// ```rust
+    /// use spicex::parser::{ConfigParser, CustomIniParser};
+    /// use spicex::ConfigValue;
+    ///
+    /// let parser = CustomIniParser::new().split_values_on(',');
+    /// let parsed = parser
+    ///     .parse("hosts = a.com, b.com, c.com\nmotto = \"fast, cheap\"\n")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     parsed["hosts"],
+    ///     ConfigValue::Array(vec![
+    ///         ConfigValue::from("a.com"),
+    ///         ConfigValue::from("b.com"),
+    ///         ConfigValue::from("c.com"),
+    ///     ])
+    /// );
+    /// assert_eq!(parsed["motto"], ConfigValue::from("fast, cheap"));
+    /// ```
+    pub fn split_values_on(mut self, separator: char) -> Self {
+        self.value_separator = Some(separator);
+        self
+    }
+
+    /// Groups keys that appear before any section header under a section named `name`.
+    ///
+    /// By default such keys stay at the top level, one level above the keys of sections.
+    /// With a default section, as in Python's `configparser`, every key is nested under a
+    /// section. Keys of an explicit `[name]` section are merged into the same object and
+    /// take precedence. On serialization, the keys of the default section are written
+    /// before the first section header.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the default section, e.g. `DEFAULT`
+    ///
+    /// # Example
+    /// ```rust
+    /// use spicex::parser::{ConfigParser, CustomIniParser};
+    /// use spicex::ConfigValue;
+    ///
+    /// let parser = CustomIniParser::new().with_default_section("DEFAULT");
+    /// let parsed = parser
+    ///     .parse("debug = true\n\n[database]\nport = 5432\n")
+    ///     .unwrap();
+    ///
+    /// let defaults = parsed["DEFAULT"].as_object().unwrap();
+    /// assert_eq!(defaults["debug"], ConfigValue::from(true));
+    /// assert!(!parsed.contains_key("debug"));
+    /// ```
+    pub fn with_default_section<S: Into<String>>(mut self, name: S) -> Self {
+        self.default_section = Some(name.into());
+        self
+    }
+
+    /// Moves the keys of the default section, if one is configured, to the top level.
+    fn ungroup_default_section(
+        &self,
+        data: &HashMap<String, ConfigValue>,
+    ) -> HashMap<String, ConfigValue> {
+        let mut ungrouped = data.clone();
+        let Some(name) = &self.default_section else {
+            return ungrouped;
+        };
+        if let Some(ConfigValue::Object(defaults)) = ungrouped.remove(name) {
+            for (key, value) in defaults {
+                ungrouped.entry(key).or_insert(value);
+            }
+        }
+        ungrouped
+    }
+}
+
+impl ConfigParser for CustomIniParser {
+    fn parse(&self, content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
+        let mut result =
+            parse_ini_content(content, self.repeated_keys_as_array, self.value_separator)?;
+
+        if let Some(name) = &self.default_section {
+            let global_keys: Vec<String> = result
+                .iter()
+                .filter(|(_, value)| !matches!(value, ConfigValue::Object(_)))
+                .map(|(key, _)| key.clone())
+                .collect();
+            let mut defaults = match result.remove(name) {
+                Some(ConfigValue::Object(section)) => section,
+                _ => HashMap::new(),
+            };
+            for key in global_keys {
+                if let Some(value) = result.remove(&key) {
+                    defaults.entry(key).or_insert(value);
+                }
+            }
+            if !defaults.is_empty() {
+                result.insert(name.clone(), ConfigValue::Object(defaults));
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
+        serialize_ini_data(&self.ungroup_default_section(data), self.value_separator)
+    }
+
+    /// Rewrites `original` with the values in `data`, keeping its comments and layout.
+    ///
+    /// Lines of keys whose value is unchanged are kept as written, changed keys are
+    /// rewritten in place, and comments directly above a removed key or section are
+    /// removed with it. New keys are appended to the end of their section and new
+    /// sections to the end of the file. If `original` is not valid INI, this falls back
+    /// to `serialize`.
+    fn serialize_update(
+        &self,
+        original: &str,
+        data: &HashMap<String, ConfigValue>,
+    ) -> ConfigResult<String> {
+        match parse_ini_content(original, self.repeated_keys_as_array, self.value_separator) {
+            Ok(previous) => Ok(update_ini_content(
+                original,
+                &previous,
+                &self.ungroup_default_section(data),
+                self.value_separator,
+            )),
+            Err(_) => self.serialize(data),
+        }
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["ini"]
+    }
+
+    fn name(&self) -> &str {
+        "INI"
+    }
+}
+
+/// XML configuration parser.
+///
+/// Parses XML documents into configuration data. The children of the root element become
+/// the top-level keys and the root element's name is ignored. Within an element:
+///
+/// - Child elements become nested objects, keyed by element name
+/// - Repeated sibling elements become arrays
+/// - Attributes become keys prefixed with `@`
+/// - Text is converted to integers, floats and booleans where possible, otherwise strings
+/// - Text mixed with attributes or child elements is stored under `#text`
+///
+/// Serialization writes the data under a `<config>` root element. Since XML cannot tell a
+/// one-element array from a single value, or a number from a numeric string, a round trip
+/// is best-effort.
+///
+/// This parser requires the `xml` feature.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "xml")]
+/// # {
+/// use spicex::parser::{XmlParser, ConfigParser};
+/// use spicex::ConfigValue;
+///
+/// let parser = XmlParser;
+/// let xml_content = r#"
+/// <config>
+///     <database host="localhost">
+///         <port>5432</port>
+///     </database>
+///     <server>api-1</server>
+///     <server>api-2</server>
+/// </config>
+/// "#;
+///
+/// let parsed = parser.parse(xml_content).unwrap();
+/// let database = parsed["database"].as_object().unwrap();
+/// assert_eq!(database["@host"], ConfigValue::from("localhost"));
+/// assert_eq!(database["port"], ConfigValue::from(5432i64));
+/// assert!(parsed["server"].as_array().is_some());
+/// # }
+/// ```
+#[cfg(feature = "xml")]
+pub struct XmlParser;
+
+#[cfg(feature = "xml")]
+impl ConfigParser for XmlParser {
+    fn parse(&self, content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
+        crate::xml::parse_document(content)
+    }
+
+    fn serialize(&self, data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
+        crate::xml::serialize_document(data)
+    }
+
+    fn supported_extensions(&self) -> &[&str] {
+        &["xml"]
+    }
+
+    fn name(&self) -> &str {
+        "XML"
+    }
+}
+
+fn parse_ini_content(
+    content: &str,
+    repeated_keys_as_array: bool,
+    value_separator: Option<char>,
+) -> ConfigResult<HashMap<String, ConfigValue>> {
     let mut result = HashMap::new();
     let mut current_section: Option<String> = None;
     let mut current_section_data = HashMap::new();
@@ -602,15 +1028,19 @@ fn parse_ini_content(content: &str) -> ConfigResult<HashMap<String, ConfigValue>
                 return Err(ConfigError::parse_error("INI", "Empty key name"));
             }
 
-            let parsed_value = parse_ini_value(value);
+            let parsed_value = match value_separator {
+                Some(separator) => parse_separated_ini_value(value, separator),
+                None => parse_ini_property_value(value),
+            };
 
-            if current_section.is_some() {
+            let target = if current_section.is_some() {
                 // We're in a section
-                current_section_data.insert(key, parsed_value);
+                &mut current_section_data
             } else {
                 // Global property
-                result.insert(key, parsed_value);
-            }
+                &mut result
+            };
+            insert_ini_value(target, key, parsed_value, repeated_keys_as_array);
         } else {
             return Err(ConfigError::parse_error(
                 "INI",
@@ -629,14 +1059,306 @@ fn parse_ini_content(content: &str) -> ConfigResult<HashMap<String, ConfigValue>
     Ok(result)
 }
 
-fn serialize_ini_data(data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
+/// Rewrites INI `original`, whose parsed form is `previous`, so that it holds `data`.
+fn update_ini_content(
+    original: &str,
+    previous: &HashMap<String, ConfigValue>,
+    data: &HashMap<String, ConfigValue>,
+    value_separator: Option<char>,
+) -> String {
+    // The new and previous values of a section
+    type Section<'a> = (
+        &'a HashMap<String, ConfigValue>,
+        &'a HashMap<String, ConfigValue>,
+    );
+
+    let empty = HashMap::new();
+    let globals: HashMap<String, ConfigValue> = data
+        .iter()
+        .filter(|(_, value)| !matches!(value, ConfigValue::Object(_)))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let section_data = |name: &str| match data.get(name) {
+        Some(ConfigValue::Object(section)) => Some(section),
+        _ => None,
+    };
+    let previous_section = |name: &str| match previous.get(name) {
+        Some(ConfigValue::Object(section)) => section,
+        _ => &empty,
+    };
+
+    let mut output = String::new();
+    // Blank and comment lines not yet attached to the key or section that follows them
+    let mut pending: Vec<&str> = Vec::new();
+    // The section being copied, or None while skipping a removed section
+    let mut current: Option<Section> = Some((&globals, previous));
+    let mut written_keys: Vec<String> = Vec::new();
+    let mut written_sections: Vec<String> = Vec::new();
+
+    let finish_section =
+        |output: &mut String, current: Option<Section>, written_keys: &[String]| {
+            if let Some((values, _)) = current {
+                for (key, value) in sorted_entries(values) {
+                    if !written_keys.contains(key) {
+                        push_ini_property(output, key, value, value_separator);
+                    }
+                }
+            }
+        };
+
+    for line in original.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+            pending.push(line);
+        } else if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            finish_section(&mut output, current, &written_keys);
+            written_keys.clear();
+
+            let name = trimmed[1..trimmed.len() - 1].trim();
+            current = section_data(name).map(|values| (values, previous_section(name)));
+            if current.is_some() {
+                flush_pending(&mut output, &mut pending, true);
+                output.push_str(line);
+                output.push('\n');
+                written_sections.push(name.to_string());
+            } else {
+                flush_pending(&mut output, &mut pending, false);
+            }
+        } else if let Some((values, previous_values)) = current {
+            let key = trimmed.split('=').next().unwrap_or_default().trim();
+            match values.get(key) {
+                None => flush_pending(&mut output, &mut pending, false),
+                Some(value) if previous_values.get(key) == Some(value) => {
+                    flush_pending(&mut output, &mut pending, true);
+                    output.push_str(line);
+                    output.push('\n');
+                    written_keys.push(key.to_string());
+                }
+                Some(value) => {
+                    flush_pending(&mut output, &mut pending, true);
+                    if !written_keys.iter().any(|written| written == key) {
+                        push_ini_property(&mut output, key, value, value_separator);
+                        written_keys.push(key.to_string());
+                    }
+                }
+            }
+        } else {
+            // A line of a removed section
+            pending.clear();
+        }
+    }
+    finish_section(&mut output, current, &written_keys);
+    flush_pending(&mut output, &mut pending, true);
+
+    for (name, value) in sorted_entries(data) {
+        if let ConfigValue::Object(section) = value {
+            if !written_sections.contains(name) {
+                if !output.is_empty() && !output.ends_with("\n\n") {
+                    output.push('\n');
+                }
+                output.push_str(&format!("[{name}]\n"));
+                for (key, value) in sorted_entries(section) {
+                    push_ini_property(&mut output, key, value, value_separator);
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Writes out `pending` lines, or drops their comments but keeps blank lines if the key
+/// or section they precede is removed.
+fn flush_pending(output: &mut String, pending: &mut Vec<&str>, keep_comments: bool) {
+    for line in pending.drain(..) {
+        if keep_comments || line.trim().is_empty() {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+}
+
+/// Inserts a parsed value, appending to an array if the key was already seen.
+fn insert_ini_value(
+    map: &mut HashMap<String, ConfigValue>,
+    key: String,
+    value: ConfigValue,
+    repeated_keys_as_array: bool,
+) {
+    if !repeated_keys_as_array {
+        map.insert(key, value);
+        return;
+    }
+
+    let mut items = match map.remove(&key) {
+        None => {
+            map.insert(key, value);
+            return;
+        }
+        Some(ConfigValue::Array(items)) => items,
+        Some(previous) => vec![previous],
+    };
+    match value {
+        ConfigValue::Array(values) => items.extend(values),
+        value => items.push(value),
+    }
+    map.insert(key, ConfigValue::Array(items));
+}
+
+/// Parses a value that may hold several elements separated by `separator`.
+fn parse_separated_ini_value(value: &str, separator: char) -> ConfigValue {
+    if unquote_ini_value(value).is_some() || !value.contains(separator) {
+        return parse_ini_property_value(value);
+    }
+    ConfigValue::Array(
+        value
+            .split(separator)
+            .map(|item| ConfigValue::String(item.trim().to_string()))
+            .collect(),
+    )
+}
+
+/// Writes a property. Arrays are joined with `value_separator` if one is set, and
+/// otherwise written as one line per element. Arrays with an element that would not
+/// survive splitting, such as one containing the separator, are also written as one
+/// line per element.
+fn push_ini_property(
+    output: &mut String,
+    key: &str,
+    value: &ConfigValue,
+    value_separator: Option<char>,
+) {
+    match (value, value_separator) {
+        (ConfigValue::Array(items), Some(separator))
+            if items
+                .iter()
+                .all(|item| splits_back_unchanged(item, separator)) =>
+        {
+            // Elements are written as is, since quotes would not survive splitting
+            let joined: Vec<String> = items.iter().map(config_value_to_ini_string).collect();
+            let separator = if separator.is_whitespace() {
+                separator.to_string()
+            } else {
+                format!("{separator} ")
+            };
+            output.push_str(&format!("{} = {}\n", key, joined.join(&separator)));
+        }
+        (ConfigValue::Array(items), _) => {
+            for item in items {
+                output.push_str(&format!(
+                    "{} = {}\n",
+                    key,
+                    format_ini_value(item, value_separator)
+                ));
+            }
+        }
+        _ => output.push_str(&format!(
+            "{} = {}\n",
+            key,
+            format_ini_value(value, value_separator)
+        )),
+    }
+}
+
+/// Returns true if an array element reads back unchanged after its array is joined with
+/// `separator` and split again.
+fn splits_back_unchanged(item: &ConfigValue, separator: char) -> bool {
+    match item {
+        ConfigValue::String(s) => {
+            s.trim() == s
+                && !s.contains(separator)
+                && !s.contains(['\n', '\r'])
+                && !s.starts_with(['"', '\''])
+                && !s.ends_with(['"', '\''])
+        }
+        _ => true,
+    }
+}
+
+/// Formats a value for writing, quoting strings that would not read back unchanged.
+fn format_ini_value(value: &ConfigValue, value_separator: Option<char>) -> String {
+    let s = match value {
+        ConfigValue::String(s) => s,
+        _ => return config_value_to_ini_string(value),
+    };
+
+    let needs_quotes = s.trim() != s
+        || s.starts_with(['"', '\''])
+        || s.contains(['\n', '\r', '\t'])
+        || value_separator.is_some_and(|separator| s.contains(separator))
+        || !matches!(parse_ini_value(s), ConfigValue::String(_));
+    if !needs_quotes {
+        return s.clone();
+    }
+
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Parses a property value. Quoted values are strings, with their escapes processed and
+/// their spacing kept; anything else has its type detected.
+fn parse_ini_property_value(value: &str) -> ConfigValue {
+    match unquote_ini_value(value) {
+        Some(s) => ConfigValue::String(s),
+        None => parse_ini_value(value),
+    }
+}
+
+/// Returns the contents of a value wrapped in single or double quotes, with `\n`, `\r`,
+/// `\t`, `\\`, `\"` and `\'` escapes processed, or None if the value is not quoted.
+fn unquote_ini_value(value: &str) -> Option<String> {
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    if value.len() < 2 || !value.ends_with(quote) {
+        return None;
+    }
+
+    let mut unquoted = String::with_capacity(value.len() - 2);
+    let mut chars = value[1..value.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unquoted.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unquoted.push('\n'),
+            Some('r') => unquoted.push('\r'),
+            Some('t') => unquoted.push('\t'),
+            Some(escaped @ ('\\' | '"' | '\'')) => unquoted.push(escaped),
+            Some(other) => {
+                unquoted.push('\\');
+                unquoted.push(other);
+            }
+            // The closing quote is escaped, so the value is not actually quoted
+            None => return None,
+        }
+    }
+    Some(unquoted)
+}
+
+fn serialize_ini_data(
+    data: &HashMap<String, ConfigValue>,
+    value_separator: Option<char>,
+) -> ConfigResult<String> {
     let mut output = String::new();
 
     // Separate root-level properties from sections
     let mut general_properties = Vec::new();
     let mut sections = Vec::new();
 
-    for (key, value) in data {
+    for (key, value) in sorted_entries(data) {
         match value {
             ConfigValue::Object(obj) => {
                 // This is a section
@@ -652,11 +1374,7 @@ fn serialize_ini_data(data: &HashMap<String, ConfigValue>) -> ConfigResult<Strin
     // Write general properties first
     let has_general_properties = !general_properties.is_empty();
     for (key, value) in general_properties {
-        output.push_str(&format!(
-            "{} = {}\n",
-            key,
-            config_value_to_ini_string(value)
-        ));
+        push_ini_property(&mut output, key, value, value_separator);
     }
 
     // Add blank line if we have both general properties and sections
@@ -672,19 +1390,15 @@ fn serialize_ini_data(data: &HashMap<String, ConfigValue>) -> ConfigResult<Strin
 
         output.push_str(&format!("[{section_name}]\n"));
 
-        for (key, value) in section_obj.iter() {
-            output.push_str(&format!(
-                "{} = {}\n",
-                key,
-                config_value_to_ini_string(value)
-            ));
+        for (key, value) in sorted_entries(section_obj) {
+            push_ini_property(&mut output, key, value, value_separator);
         }
     }
 
     Ok(output)
 }
 
-fn parse_ini_value(value: &str) -> ConfigValue {
+pub(crate) fn parse_ini_value(value: &str) -> ConfigValue {
     // Try to parse as different types
 
     // Try integer first (before boolean to avoid "0" and "1" being parsed as booleans)
@@ -726,6 +1440,13 @@ fn config_value_to_ini_string(value: &ConfigValue) -> String {
     }
 }
 
+/// Returns the entries of a map sorted by key, so serialized output is deterministic.
+fn sorted_entries(map: &HashMap<String, ConfigValue>) -> Vec<(&String, &ConfigValue)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
 // Helper functions for JSON value conversion
 fn convert_json_value(value: serde_json::Value) -> ConfigResult<HashMap<String, ConfigValue>> {
     match value {
@@ -740,7 +1461,7 @@ fn convert_json_value(value: serde_json::Value) -> ConfigResult<HashMap<String,
     }
 }
 
-fn json_to_config_value(value: serde_json::Value) -> ConfigValue {
+pub(crate) fn json_to_config_value(value: serde_json::Value) -> ConfigValue {
     match value {
         serde_json::Value::String(s) => ConfigValue::String(s),
         serde_json::Value::Number(n) => {
@@ -767,7 +1488,7 @@ fn json_to_config_value(value: serde_json::Value) -> ConfigValue {
     }
 }
 
-fn config_value_to_json(value: &ConfigValue) -> serde_json::Value {
+pub(crate) fn config_value_to_json(value: &ConfigValue) -> serde_json::Value {
     match value {
         ConfigValue::String(s) => serde_json::Value::String(s.clone()),
         ConfigValue::Integer(i) => serde_json::Value::Number(serde_json::Number::from(*i)),
@@ -779,8 +1500,8 @@ fn config_value_to_json(value: &ConfigValue) -> serde_json::Value {
             serde_json::Value::Array(arr.iter().map(config_value_to_json).collect())
         }
         ConfigValue::Object(obj) => {
-            let map: serde_json::Map<String, serde_json::Value> = obj
-                .iter()
+            let map: serde_json::Map<String, serde_json::Value> = sorted_entries(obj)
+                .into_iter()
                 .map(|(k, v)| (k.clone(), config_value_to_json(v)))
                 .collect();
             serde_json::Value::Object(map)
@@ -811,7 +1532,7 @@ fn convert_yaml_value(value: serde_yaml::Value) -> ConfigResult<HashMap<String,
     }
 }
 
-fn yaml_to_config_value(value: serde_yaml::Value) -> ConfigValue {
+pub(crate) fn yaml_to_config_value(value: serde_yaml::Value) -> ConfigValue {
     match value {
         serde_yaml::Value::String(s) => ConfigValue::String(s),
         serde_yaml::Value::Number(n) => {
@@ -868,7 +1589,7 @@ fn config_value_to_yaml(value: &ConfigValue) -> serde_yaml::Value {
         }
         ConfigValue::Object(obj) => {
             let mut map = serde_yaml::Mapping::new();
-            for (k, v) in obj {
+            for (k, v) in sorted_entries(obj) {
                 map.insert(
                     serde_yaml::Value::String(k.clone()),
                     config_value_to_yaml(v),
@@ -896,7 +1617,7 @@ fn convert_toml_value(value: toml::Value) -> ConfigResult<HashMap<String, Config
     }
 }
 
-fn toml_to_config_value(value: toml::Value) -> ConfigValue {
+pub(crate) fn toml_to_config_value(value: toml::Value) -> ConfigValue {
     match value {
         toml::Value::String(s) => ConfigValue::String(s),
         toml::Value::Integer(i) => ConfigValue::Integer(i),
@@ -930,7 +1651,7 @@ fn config_value_to_toml(value: &ConfigValue) -> toml::Value {
         }
         ConfigValue::Object(obj) => {
             let mut table = toml::map::Map::new();
-            for (k, v) in obj {
+            for (k, v) in sorted_entries(obj) {
                 table.insert(k.clone(), config_value_to_toml(v));
             }
             toml::Value::Table(table)
@@ -943,6 +1664,7 @@ fn config_value_to_toml(value: &ConfigValue) -> toml::Value {
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
 
@@ -1232,6 +1954,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_json_serialization_compact() {
+        let data = match crate::config_value!({
+            "database": { "host": "localhost", "port": 5432 },
+            "debug": true
+        }) {
+            ConfigValue::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        let pretty = JsonParser.serialize(&data).unwrap();
+        assert!(pretty.contains("\n  \"database\": {"));
+
+        let parser = CompactJsonParser;
+        let compact = parser.serialize(&data).unwrap();
+        assert_eq!(
+            compact,
+            r#"{"database":{"host":"localhost","port":5432},"debug":true}"#
+        );
+        assert_eq!(parser.parse(&compact).unwrap(), data);
+    }
+
     #[test]
     fn test_json_serialization_round_trip() {
         let parser = JsonParser;
@@ -1488,6 +2232,36 @@ key: value
         }
     }
 
+    #[test]
+    fn test_yaml_parser_multiple_documents() {
+        let parser = YamlParser;
+        let yaml_content = r#"
+---
+database:
+  host: localhost
+  port: 5432
+debug: true
+---
+---
+database:
+  host: db.internal
+timeout: 30
+"#;
+
+        let result = parser.parse(yaml_content).unwrap();
+        assert_eq!(result.len(), 3);
+        assert_eq!(result["debug"], ConfigValue::from(true));
+        assert_eq!(result["timeout"], ConfigValue::from(30i64));
+        // Top-level keys of later documents replace earlier ones as a whole
+        assert_eq!(
+            result["database"],
+            crate::config_value!({ "host": "db.internal" })
+        );
+
+        let error = parser.parse("debug: true\n---\n- item\n").unwrap_err();
+        assert!(error.to_string().contains("Document 2 must be a mapping"));
+    }
+
     #[test]
     fn test_yaml_parser_empty_object() {
         let parser = YamlParser;
@@ -2371,7 +3145,7 @@ string_number = 123abc
         );
         assert_eq!(
             result.get("quoted_string"),
-            Some(&ConfigValue::String("\"quoted value\"".to_string()))
+            Some(&ConfigValue::String("quoted value".to_string()))
         );
         assert_eq!(
             result.get("string_with_spaces"),
@@ -2387,6 +3161,95 @@ string_number = 123abc
         );
     }
 
+    #[test]
+    fn test_ini_parser_quoted_values() {
+        let parser = IniParser;
+        let ini_content = r#"
+name = "John Doe"
+padded = "  spaced out  "
+single = 'it\'s here'
+escapes = "line one\nline\ttwo \"quoted\" \\ end"
+unknown_escape = "C:\data"
+path = C:\temp
+number_string = "8080"
+port = 8080
+unbalanced = "open
+escaped_close = "ends with \"
+        "#;
+
+        let result = parser.parse(ini_content).unwrap();
+        assert_eq!(result["name"], ConfigValue::from("John Doe"));
+        assert_eq!(result["padded"], ConfigValue::from("  spaced out  "));
+        assert_eq!(result["single"], ConfigValue::from("it's here"));
+        assert_eq!(
+            result["escapes"],
+            ConfigValue::from("line one\nline\ttwo \"quoted\" \\ end")
+        );
+        assert_eq!(result["unknown_escape"], ConfigValue::from("C:\\data"));
+        assert_eq!(result["path"], ConfigValue::from("C:\\temp"));
+        assert_eq!(result["number_string"], ConfigValue::from("8080"));
+        assert_eq!(result["port"], ConfigValue::Integer(8080));
+        assert_eq!(result["unbalanced"], ConfigValue::from("\"open"));
+        assert_eq!(
+            result["escaped_close"],
+            ConfigValue::from("\"ends with \\\"")
+        );
+
+        let serialized = parser.serialize(&result).unwrap();
+        assert!(serialized.contains("name = John Doe\n"));
+        assert!(serialized.contains("padded = \"  spaced out  \"\n"));
+        assert!(serialized.contains("number_string = \"8080\"\n"));
+        assert!(serialized.contains("path = C:\\temp\n"));
+        assert!(
+            serialized.contains("escapes = \"line one\\nline\\ttwo \\\"quoted\\\" \\\\ end\"\n")
+        );
+        assert_eq!(parser.parse(&serialized).unwrap(), result);
+    }
+
+    #[test]
+    fn test_ini_parser_default_section() {
+        let ini_content = r#"
+debug = true
+name = global
+
+[DEFAULT]
+name = explicit
+region = eu
+
+[database]
+port = 5432
+        "#;
+
+        // Without a default section, globals stay at the top level
+        let plain = IniParser.parse(ini_content).unwrap();
+        assert_eq!(plain["debug"], ConfigValue::from(true));
+
+        let parser = CustomIniParser::new().with_default_section("DEFAULT");
+        let result = parser.parse(ini_content).unwrap();
+        assert_eq!(
+            result["DEFAULT"],
+            crate::config_value!({ "debug": true, "name": "explicit", "region": "eu" })
+        );
+        assert_eq!(result["database"], crate::config_value!({ "port": 5432 }));
+        assert_eq!(result.len(), 2);
+
+        let serialized = parser.serialize(&result).unwrap();
+        assert!(serialized.starts_with("debug = true\nname = explicit\nregion = eu\n"));
+        assert!(!serialized.contains("[DEFAULT]"));
+        assert_eq!(parser.parse(&serialized).unwrap(), result);
+
+        let updated = parser
+            .serialize_update(
+                "; Globals\ndebug = true\n\n[database]\nport = 5432\n",
+                &result,
+            )
+            .unwrap();
+        assert_eq!(
+            updated,
+            "; Globals\ndebug = true\nname = explicit\nregion = eu\n\n[database]\nport = 5432\n"
+        );
+    }
+
     #[test]
     fn test_ini_parser_invalid_syntax() {
         let parser = IniParser;
@@ -2546,6 +3409,202 @@ enabled = true
         assert_eq!(parsed, reparsed);
     }
 
+    #[test]
+    fn test_ini_repeated_keys() {
+        let ini_content = r#"
+server = a
+server = b
+server = c
+name = first
+
+[database]
+replica = db-1
+replica = db-2
+port = 5432
+
+[cache]
+replica = cache-1
+"#;
+
+        let parsed = IniParser.parse(ini_content).unwrap();
+        assert_eq!(
+            parsed["server"],
+            ConfigValue::Array(vec![
+                ConfigValue::from("a"),
+                ConfigValue::from("b"),
+                ConfigValue::from("c")
+            ])
+        );
+        assert_eq!(parsed["name"], ConfigValue::from("first"));
+        let database = parsed["database"].as_object().unwrap();
+        assert_eq!(
+            database["replica"],
+            ConfigValue::Array(vec![ConfigValue::from("db-1"), ConfigValue::from("db-2")])
+        );
+        assert_eq!(database["port"], ConfigValue::Integer(5432));
+        let cache = parsed["cache"].as_object().unwrap();
+        assert_eq!(cache["replica"], ConfigValue::from("cache-1"));
+
+        let serialized = IniParser.serialize(&parsed).unwrap();
+        assert!(serialized.contains("server = a\nserver = b\nserver = c\n"));
+        assert!(serialized.contains("replica = db-1\nreplica = db-2\n"));
+        assert_eq!(IniParser.parse(&serialized).unwrap(), parsed);
+
+        let last_wins = CustomIniParser::new()
+            .with_repeated_keys_as_array(false)
+            .parse(ini_content)
+            .unwrap();
+        assert_eq!(last_wins["server"], ConfigValue::from("c"));
+        let database = last_wins["database"].as_object().unwrap();
+        assert_eq!(database["replica"], ConfigValue::from("db-2"));
+    }
+
+    #[test]
+    fn test_ini_split_values() {
+        let ini_content = r#"
+hosts = a.com, b.com ,c.com
+single = a.com
+port = 8080
+quoted = "fast, cheap"
+
+[database]
+replicas = db-1,db-2
+replicas = db-3
+"#;
+
+        // Without a separator the values stay single strings
+        let plain = IniParser.parse(ini_content).unwrap();
+        assert_eq!(plain["hosts"], ConfigValue::from("a.com, b.com ,c.com"));
+        assert_eq!(plain["quoted"], ConfigValue::from("fast, cheap"));
+
+        let parser = CustomIniParser::new().split_values_on(',');
+        let parsed = parser.parse(ini_content).unwrap();
+        assert_eq!(
+            parsed["hosts"],
+            ConfigValue::Array(vec![
+                ConfigValue::from("a.com"),
+                ConfigValue::from("b.com"),
+                ConfigValue::from("c.com")
+            ])
+        );
+        assert_eq!(parsed["single"], ConfigValue::from("a.com"));
+        assert_eq!(parsed["port"], ConfigValue::Integer(8080));
+        assert_eq!(parsed["quoted"], ConfigValue::from("fast, cheap"));
+        let database = parsed["database"].as_object().unwrap();
+        assert_eq!(
+            database["replicas"],
+            ConfigValue::Array(vec![
+                ConfigValue::from("db-1"),
+                ConfigValue::from("db-2"),
+                ConfigValue::from("db-3")
+            ])
+        );
+
+        let serialized = parser.serialize(&parsed).unwrap();
+        assert!(serialized.contains("hosts = a.com, b.com, c.com\n"));
+        assert!(serialized.contains("quoted = \"fast, cheap\"\n"));
+        assert_eq!(parser.parse(&serialized).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_ini_separated_values_round_trip_elements_with_separator() {
+        let parser = CustomIniParser::new().split_values_on(',');
+        let mut data = HashMap::new();
+        data.insert(
+            "mottos".to_string(),
+            ConfigValue::Array(vec![
+                ConfigValue::from("fast, cheap"),
+                ConfigValue::from("good"),
+            ]),
+        );
+        data.insert(
+            "hosts".to_string(),
+            ConfigValue::Array(vec![ConfigValue::from("a.com"), ConfigValue::from("b.com")]),
+        );
+
+        // Arrays that cannot be joined fall back to one line per element
+        let serialized = parser.serialize(&data).unwrap();
+        assert!(serialized.contains("mottos = \"fast, cheap\"\nmottos = good\n"));
+        assert!(serialized.contains("hosts = a.com, b.com\n"));
+        assert_eq!(parser.parse(&serialized).unwrap(), data);
+
+        let updated = parser
+            .serialize_update("mottos = one, two\nhosts = a.com\n", &data)
+            .unwrap();
+        assert_eq!(parser.parse(&updated).unwrap(), data);
+    }
+
+    #[test]
+    fn test_ini_serialize_update_keeps_comments() {
+        let parser = IniParser;
+        let original = r#"# Global settings
+name = my-app
+; Removed with its key
+legacy = true
+
+[database]
+; Primary host
+host = localhost   
+; Port to connect on
+port = 5432
+replica = db-1
+replica = db-2
+
+# Cache settings
+[cache]
+ttl = 60
+
+# Trailing comment
+"#;
+        let mut data = parser.parse(original).unwrap();
+        data.remove("legacy");
+        data.remove("cache");
+        data.insert("debug".to_string(), ConfigValue::from(true));
+        let database = match data.get_mut("database") {
+            Some(ConfigValue::Object(database)) => database,
+            _ => unreachable!(),
+        };
+        database.insert("port".to_string(), ConfigValue::Integer(6543));
+        database.insert(
+            "replica".to_string(),
+            ConfigValue::Array(vec![ConfigValue::from("db-3"), ConfigValue::from("db-4")]),
+        );
+        database.insert("ssl".to_string(), ConfigValue::from(true));
+        data.insert(
+            "logging".to_string(),
+            crate::config_value!({ "level": "info" }),
+        );
+
+        let updated = parser.serialize_update(original, &data).unwrap();
+        assert_eq!(
+            updated,
+            r#"# Global settings
+name = my-app
+debug = true
+
+[database]
+; Primary host
+host = localhost   
+; Port to connect on
+port = 6543
+replica = db-3
+replica = db-4
+ssl = true
+
+
+# Trailing comment
+
+[logging]
+level = info
+"#
+        );
+        assert_eq!(parser.parse(&updated).unwrap(), data);
+
+        // Content that does not parse is replaced
+        let updated = parser.serialize_update("not ini", &data).unwrap();
+        assert_eq!(updated, parser.serialize(&data).unwrap());
+    }
+
     #[test]
     fn test_ini_complex_values_handling() {
         let parser = IniParser;
@@ -2572,10 +3631,13 @@ enabled = true
         let serialized = parser.serialize(&data).unwrap();
         let reparsed = parser.parse(&serialized).unwrap();
 
-        // Arrays and nested objects become string representations
+        // Arrays are written as repeated keys and read back as arrays
         assert_eq!(
             reparsed.get("array_value"),
-            Some(&ConfigValue::String("[array]".to_string()))
+            Some(&ConfigValue::Array(vec![
+                ConfigValue::String("item1".to_string()),
+                ConfigValue::Integer(2),
+            ]))
         );
         // The nested object becomes a section, so it should be preserved
         if let Some(ConfigValue::Object(obj)) = reparsed.get("object_value") {
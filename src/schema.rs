@@ -0,0 +1,427 @@
+//! Schema-based validation of configuration values.
+
+use crate::config::Spice;
+use crate::error::{ConfigError, ConfigResult};
+use crate::value::ConfigValue;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+/// The type a configuration value is expected to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    /// A string value
+    String,
+    /// An integer value
+    Integer,
+    /// A floating point value; integers are accepted as well
+    Float,
+    /// A boolean value
+    Boolean,
+    /// An array of values
+    Array,
+    /// An object/map of key-value pairs
+    Object,
+}
+
+impl ValueType {
+    fn accepts(self, value: &ConfigValue) -> bool {
+        matches!(
+            (self, value),
+            (ValueType::String, ConfigValue::String(_))
+                | (ValueType::Integer, ConfigValue::Integer(_))
+                | (
+                    ValueType::Float,
+                    ConfigValue::Float(_) | ConfigValue::Integer(_)
+                )
+                | (ValueType::Boolean, ConfigValue::Boolean(_))
+                | (ValueType::Array, ConfigValue::Array(_))
+                | (ValueType::Object, ConfigValue::Object(_))
+        )
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValueType::String => "String",
+            ValueType::Integer => "Integer",
+            ValueType::Float => "Float",
+            ValueType::Boolean => "Boolean",
+            ValueType::Array => "Array",
+            ValueType::Object => "Object",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Rule {
+    Required,
+    Type(ValueType),
+    Range(Bound<f64>, Bound<f64>),
+    OneOf(Vec<ConfigValue>),
+    #[cfg(feature = "regex")]
+    Pattern(regex::Regex),
+}
+
+impl Rule {
+    /// Checks a present value, returning a description of the violation if any.
+    fn check(&self, value: &ConfigValue) -> Option<String> {
+        match self {
+            Rule::Required => None,
+            Rule::Type(expected) => (!expected.accepts(value))
+                .then(|| format!("expected {expected}, found {}", value.type_name())),
+            Rule::Range(start, end) => match value.as_f64() {
+                Some(number) if (*start, *end).contains(&number) => None,
+                Some(number) => Some(format!(
+                    "{number} is outside the range {}",
+                    describe_range(*start, *end)
+                )),
+                None => Some(format!(
+                    "expected a number in the range {}, found {}",
+                    describe_range(*start, *end),
+                    value.type_name()
+                )),
+            },
+            Rule::OneOf(allowed) => (!allowed.contains(value)).then(|| {
+                let allowed: Vec<String> = allowed.iter().map(ToString::to_string).collect();
+                format!("'{value}' is not one of: {}", allowed.join(", "))
+            }),
+            #[cfg(feature = "regex")]
+            Rule::Pattern(pattern) => match value.as_str() {
+                Some(text) if pattern.is_match(text) => None,
+                Some(text) => Some(format!(
+                    "'{text}' does not match pattern '{}'",
+                    pattern.as_str()
+                )),
+                None => Some(format!(
+                    "expected a String matching '{}', found {}",
+                    pattern.as_str(),
+                    value.type_name()
+                )),
+            },
+        }
+    }
+}
+
+fn describe_range(start: Bound<f64>, end: Bound<f64>) -> String {
+    let start = match start {
+        Bound::Included(n) => format!("[{n}"),
+        Bound::Excluded(n) => format!("({n}"),
+        Bound::Unbounded => "(-inf".to_string(),
+    };
+    let end = match end {
+        Bound::Included(n) => format!("{n}]"),
+        Bound::Excluded(n) => format!("{n})"),
+        Bound::Unbounded => "inf)".to_string(),
+    };
+    format!("{start}, {end}")
+}
+
+/// A set of per-key rules that configuration values are checked against.
+///
+/// Rules apply to the merged value of a key as returned by `Spice::get`. Apart from
+/// `required`, rules are only checked when the key has a value, so optional settings
+/// can still be constrained.
+///
+/// # Example
+/// ```
+/// use spicex::{ConfigSchema, ConfigValue, Spice, ValueType};
+///
+/// let schema = ConfigSchema::new()
+///     .required("server.port")
+///     .expect_type("server.port", ValueType::Integer)
+///     .range("server.port", 1.0..=65535.0)
+///     .one_of("log.level", ["debug", "info", "warn", "error"]);
+///
+/// let mut spice = Spice::new();
+/// spice.set_default("server.port", ConfigValue::from(8080i64)).unwrap();
+/// spice.set_default("log.level", ConfigValue::from("info")).unwrap();
+/// assert!(spice.validate(&schema).is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSchema {
+    rules: Vec<(String, Rule)>,
+}
+
+impl ConfigSchema {
+    /// Creates a schema with no rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires `key` to have a value.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key that must be present
+    pub fn required(self, key: impl Into<String>) -> Self {
+        self.rule(key, Rule::Required)
+    }
+
+    /// Requires the value of `key` to have the given type.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to check
+    /// * `value_type` - The expected type; `ValueType::Float` also accepts integers
+    pub fn expect_type(self, key: impl Into<String>, value_type: ValueType) -> Self {
+        self.rule(key, Rule::Type(value_type))
+    }
+
+    /// Requires the value of `key` to be a number within `range`.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to check
+    /// * `range` - The accepted range, e.g. `1.0..=65535.0` or `0.0..`
+    pub fn range(self, key: impl Into<String>, range: impl RangeBounds<f64>) -> Self {
+        let rule = Rule::Range(range.start_bound().cloned(), range.end_bound().cloned());
+        self.rule(key, rule)
+    }
+
+    /// Requires the value of `key` to equal one of `allowed`.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to check
+    /// * `allowed` - The accepted values
+    pub fn one_of<V>(self, key: impl Into<String>, allowed: impl IntoIterator<Item = V>) -> Self
+    where
+        V: Into<ConfigValue>,
+    {
+        let allowed = allowed.into_iter().map(Into::into).collect();
+        self.rule(key, Rule::OneOf(allowed))
+    }
+
+    /// Requires the value of `key` to be a string matching the regular expression `pattern`.
+    /// Available with the `regex` feature.
+    ///
+    /// The pattern matches anywhere in the value; anchor it with `^` and `$` to match the
+    /// whole value. Matching takes time linear in the length of the value.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to check
+    /// * `pattern` - A regular expression in the syntax of the `regex` crate
+    ///
+    /// # Errors
+    /// * `ConfigError::InvalidValue` - If `pattern` is not a valid regular expression
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::{ConfigSchema, ConfigValue, Spice};
+    ///
+    /// let schema = ConfigSchema::new()
+    ///     .matches_regex("database.url", r"^postgres://")
+    ///     .unwrap();
+    ///
+    /// let mut spice = Spice::new();
+    /// spice.set("database.url", ConfigValue::from("mysql://db")).unwrap();
+    /// assert!(spice.validate(&schema).is_err());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn matches_regex(self, key: impl Into<String>, pattern: &str) -> ConfigResult<Self> {
+        let pattern = regex::Regex::new(pattern)
+            .map_err(|e| ConfigError::invalid_value(format!("invalid pattern '{pattern}': {e}")))?;
+        Ok(self.rule(key, Rule::Pattern(pattern)))
+    }
+
+    fn rule(mut self, key: impl Into<String>, rule: Rule) -> Self {
+        self.rules.push((key.into(), rule));
+        self
+    }
+}
+
+impl Spice {
+    /// Validates the merged configuration against a schema.
+    ///
+    /// Every rule is checked, so the error lists all violations at once rather than
+    /// stopping at the first.
+    ///
+    /// # Arguments
+    /// * `schema` - The rules to check
+    ///
+    /// # Returns
+    /// * `ConfigResult<()>` - Ok if every rule is satisfied
+    ///
+    /// # Errors
+    /// * `ConfigError::Validation` - One "key: message" entry per violated rule
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::{ConfigSchema, ConfigValue, Spice, ValueType};
+    ///
+    /// let mut spice = Spice::new();
+    /// spice.set_default("server.port", ConfigValue::from("http")).unwrap();
+    ///
+    /// let schema = ConfigSchema::new()
+    ///     .expect_type("server.port", ValueType::Integer)
+    ///     .required("server.host");
+    ///
+    /// let error = spice.validate(&schema).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Validation failed: server.port: expected Integer, found String; \
+    ///      server.host: required key is missing"
+    /// );
+    /// ```
+    pub fn validate(&self, schema: &ConfigSchema) -> ConfigResult<()> {
+        let mut violations = Vec::new();
+
+        for (key, rule) in &schema.rules {
+            let message = match self.get(key) {
+                Ok(Some(value)) => rule.check(&value),
+                Ok(None) => {
+                    matches!(rule, Rule::Required).then(|| "required key is missing".to_string())
+                }
+                Err(error) => Some(error.to_string()),
+            };
+            if let Some(message) = message {
+                violations.push(format!("{key}: {message}"));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::validation(violations))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn violations(spice: &Spice, schema: &ConfigSchema) -> Vec<String> {
+        match spice.validate(schema) {
+            Ok(()) => Vec::new(),
+            Err(ConfigError::Validation { violations }) => violations,
+            Err(other) => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_reports_every_violation() {
+        let mut spice = Spice::new();
+        spice.set("server.port", ConfigValue::from("8080")).unwrap();
+        spice
+            .set("server.workers", ConfigValue::from(0i64))
+            .unwrap();
+        spice
+            .set("log.level", ConfigValue::from("verbose"))
+            .unwrap();
+        spice.set("ratio", ConfigValue::from(1.5)).unwrap();
+
+        let schema = ConfigSchema::new()
+            .expect_type("server.port", ValueType::Integer)
+            .range("server.workers", 1.0..=64.0)
+            .one_of("log.level", ["debug", "info"])
+            .range("ratio", ..1.0)
+            .required("server.host");
+
+        assert_eq!(
+            violations(&spice, &schema),
+            vec![
+                "server.port: expected Integer, found String",
+                "server.workers: 0 is outside the range [1, 64]",
+                "log.level: 'verbose' is not one of: debug, info",
+                "ratio: 1.5 is outside the range (-inf, 1)",
+                "server.host: required key is missing",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        let mut spice = Spice::new();
+        spice
+            .set("server.port", ConfigValue::from(8080i64))
+            .unwrap();
+        spice.set("timeout", ConfigValue::from(30i64)).unwrap();
+        spice.set("debug", ConfigValue::from(true)).unwrap();
+        spice
+            .set(
+                "tags",
+                ConfigValue::Array(vec![ConfigValue::from("a"), ConfigValue::from("b")]),
+            )
+            .unwrap();
+        spice
+            .set("labels", ConfigValue::Object(HashMap::new()))
+            .unwrap();
+
+        let schema = ConfigSchema::new()
+            .required("server.port")
+            .expect_type("server.port", ValueType::Integer)
+            .range("server.port", 1.0..=65535.0)
+            .expect_type("timeout", ValueType::Float)
+            .expect_type("debug", ValueType::Boolean)
+            .one_of("debug", [true])
+            .expect_type("tags", ValueType::Array)
+            .expect_type("labels", ValueType::Object)
+            // Rules other than `required` skip absent keys
+            .expect_type("missing", ValueType::String)
+            .range("missing", 0.0..1.0);
+
+        assert!(spice.validate(&schema).is_ok());
+    }
+
+    #[test]
+    fn test_validate_checks_value_kind_for_range() {
+        let mut spice = Spice::new();
+        spice.set("port", ConfigValue::from("high")).unwrap();
+
+        let schema = ConfigSchema::new().range("port", 0.0..);
+
+        let error = spice.validate(&schema).unwrap_err();
+        assert!(error.is_validation_error());
+        assert_eq!(
+            violations(&spice, &schema),
+            vec!["port: expected a number in the range [0, inf), found String"]
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_validate_checks_pattern() {
+        let mut spice = Spice::new();
+        spice
+            .set("database.url", ConfigValue::from("mysql://db"))
+            .unwrap();
+        spice.set("name", ConfigValue::from(42i64)).unwrap();
+        spice.set("host", ConfigValue::from("db-1.local")).unwrap();
+        // Patterns that backtracking engines take exponential time on stay fast
+        spice
+            .set(
+                "slow",
+                ConfigValue::from(format!("{}b", "a".repeat(100_000))),
+            )
+            .unwrap();
+
+        let schema = ConfigSchema::new()
+            .matches_regex("database.url", r"^postgres://")
+            .unwrap()
+            .matches_regex("name", "^[a-z]+$")
+            .unwrap()
+            .matches_regex("host", r"^[a-z0-9.-]+$")
+            .unwrap()
+            .matches_regex("slow", "^(a|a)*$")
+            .unwrap();
+
+        assert_eq!(
+            violations(&spice, &schema),
+            vec![
+                "database.url: 'mysql://db' does not match pattern '^postgres://'",
+                "name: expected a String matching '^[a-z]+$', found Integer",
+                &format!(
+                    "slow: '{}b' does not match pattern '^(a|a)*$'",
+                    "a".repeat(100_000)
+                ),
+            ]
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_invalid_regex_is_rejected() {
+        let result = ConfigSchema::new().matches_regex("name", "[a-");
+        assert!(matches!(result, Err(ConfigError::InvalidValue(_))));
+    }
+}
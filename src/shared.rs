@@ -0,0 +1,222 @@
+//! Sharing one configuration between threads.
+
+use crate::config::Spice;
+use crate::diff::ConfigDiff;
+use crate::error::ConfigResult;
+use crate::value::ConfigValue;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A handle to a `Spice` instance that many threads can read at once.
+///
+/// Cloning the handle is cheap and every clone refers to the same configuration. Reads
+/// take a shared lock, so they run concurrently and need only `&self`. When a watched
+/// file has changed, the first read after the change takes the exclusive lock to reload
+/// the configuration, and later reads see the new values.
+///
+/// # Example
+/// ```rust
+/// use spicex::{ConfigValue, SharedSpice, Spice};
+/// use std::thread;
+///
+/// let mut spice = Spice::new();
+/// spice.set("server.port", ConfigValue::from(8080i64)).unwrap();
+/// let shared = SharedSpice::new(spice);
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let shared = shared.clone();
+///         thread::spawn(move || shared.get_int("server.port").unwrap())
+///     })
+///     .collect();
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), Some(8080));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedSpice {
+    inner: Arc<RwLock<Spice>>,
+}
+
+impl SharedSpice {
+    /// Wraps a configuration so it can be shared between threads.
+    ///
+    /// Set up file watching on `spice` before wrapping it, or later through `write`.
+    pub fn new(spice: Spice) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(spice)),
+        }
+    }
+
+    /// Locks the configuration for reading, first reloading it if a watched file changed.
+    ///
+    /// Any `&self` method of `Spice` can be called through the returned guard. Other
+    /// readers are not blocked while the guard is held, but writers and reloads are.
+    ///
+    /// # Returns
+    /// * `ConfigResult<RwLockReadGuard<Spice>>` - The locked configuration
+    ///
+    /// # Errors
+    /// * `ConfigError::Io` - If a configuration file cannot be read during reload
+    /// * `ConfigError::Parse` - If a configuration file cannot be parsed during reload
+    pub fn read(&self) -> ConfigResult<RwLockReadGuard<'_, Spice>> {
+        let guard = self.lock_read();
+        if !guard.reload_pending() {
+            return Ok(guard);
+        }
+        drop(guard);
+
+        // Another thread may reload first, in which case this does nothing
+        self.write().check_and_reload()?;
+        Ok(self.lock_read())
+    }
+
+    /// Locks the configuration for writing, e.g. to set values or add layers.
+    ///
+    /// All other readers and writers wait until the guard is dropped.
+    pub fn write(&self) -> RwLockWriteGuard<'_, Spice> {
+        // A panic while the lock was held cannot leave the layers half-updated in a way
+        // later reads would misinterpret, so a poisoned lock is recovered
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Gets a configuration value by key.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to retrieve
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<ConfigValue>>` - The value if found
+    pub fn get(&self, key: &str) -> ConfigResult<Option<ConfigValue>> {
+        self.read()?.get(key)
+    }
+
+    /// Gets a configuration value as a string.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to retrieve
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<String>>` - The string value if found
+    pub fn get_string(&self, key: &str) -> ConfigResult<Option<String>> {
+        self.read()?.get_string_ref(key)
+    }
+
+    /// Gets a configuration value as an integer.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to retrieve
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<i64>>` - The integer value if found and convertible
+    pub fn get_int(&self, key: &str) -> ConfigResult<Option<i64>> {
+        self.read()?.get_int_ref(key)
+    }
+
+    /// Gets a configuration value as a floating point number.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to retrieve
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<f64>>` - The float value if found and convertible
+    pub fn get_float(&self, key: &str) -> ConfigResult<Option<f64>> {
+        self.read()?.get_float(key)
+    }
+
+    /// Gets a configuration value as a boolean.
+    ///
+    /// # Arguments
+    /// * `key` - The configuration key to retrieve
+    ///
+    /// # Returns
+    /// * `ConfigResult<Option<bool>>` - The boolean value if found and convertible
+    pub fn get_bool(&self, key: &str) -> ConfigResult<Option<bool>> {
+        self.read()?.get_bool_ref(key)
+    }
+
+    /// Re-reads every configuration file layer, as `Spice::reload` does.
+    ///
+    /// # Returns
+    /// * `ConfigResult<ConfigDiff>` - The keys whose values changed
+    ///
+    /// # Errors
+    /// * `ConfigError::Io` - If a configuration file cannot be read
+    /// * `ConfigError::Parse` - If a configuration file cannot be parsed
+    pub fn reload(&self) -> ConfigResult<ConfigDiff> {
+        self.write().reload()
+    }
+
+    fn lock_read(&self) -> RwLockReadGuard<'_, Spice> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<Spice> for SharedSpice {
+    fn from(spice: Spice) -> Self {
+        Self::new(spice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_reads_and_writes() {
+        let mut spice = Spice::new();
+        spice
+            .set_default("server.port", ConfigValue::from(8080i64))
+            .unwrap();
+        spice.set("debug", ConfigValue::from("yes")).unwrap();
+        let shared = SharedSpice::from(spice);
+
+        let other = shared.clone();
+        std::thread::spawn(move || {
+            other
+                .write()
+                .set("server.port", ConfigValue::from(9090i64))
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(shared.get_int("server.port").unwrap(), Some(9090));
+        assert_eq!(
+            shared.get_string("server.port").unwrap(),
+            Some("9090".into())
+        );
+        assert_eq!(shared.get_float("server.port").unwrap(), Some(9090.0));
+        assert_eq!(shared.get_bool("debug").unwrap(), Some(true));
+        assert!(shared.get_int("debug").unwrap_err().is_type_conversion());
+        assert_eq!(shared.get("missing").unwrap(), None);
+        assert!(shared.read().unwrap().is_set("server.port"));
+    }
+
+    #[test]
+    fn test_shared_reloads_pending_changes_on_read() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        std::fs::write(&config_path, r#"{"server": {"port": 8080}}"#).unwrap();
+
+        let mut spice = Spice::new();
+        spice.set_config_file(&config_path).unwrap();
+        spice.watch_config().unwrap();
+        let needs_reload = spice.needs_reload_flag();
+        let shared = SharedSpice::new(spice);
+        assert_eq!(shared.get_int("server.port").unwrap(), Some(8080));
+
+        // Simulate the file watcher noticing a change
+        std::fs::write(&config_path, r#"{"server": {"port": 9090}}"#).unwrap();
+        needs_reload.store(true, std::sync::atomic::Ordering::SeqCst);
+
+        assert_eq!(shared.get_int("server.port").unwrap(), Some(9090));
+        assert!(!shared.read().unwrap().reload_pending());
+
+        std::fs::write(&config_path, r#"{"server": {"port": 7070}}"#).unwrap();
+        let diff = shared.reload().unwrap();
+        assert!(diff.contains("server.port"));
+        assert_eq!(shared.get_int("server.port").unwrap(), Some(7070));
+    }
+}
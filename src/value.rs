@@ -124,6 +124,76 @@ impl ConfigValue {
             ConfigValue::Null => "Null",
         }
     }
+
+    /// Converts the value into a `serde_json::Value`.
+    /// Floats that JSON cannot represent, such as NaN, become `null`.
+    ///
+    /// # Example
+    /// ```
+    /// use spicex::ConfigValue;
+    ///
+    /// let value = ConfigValue::Array(vec![ConfigValue::from(1i64), ConfigValue::from("two")]);
+    /// assert_eq!(value.into_json(), serde_json::json!([1, "two"]));
+    /// ```
+    pub fn into_json(self) -> serde_json::Value {
+        crate::parser::config_value_to_json(&self)
+    }
+
+    fn is_scalar(&self) -> bool {
+        !matches!(self, ConfigValue::Array(_) | ConfigValue::Object(_))
+    }
+}
+
+/// Formats the value for logs and error messages.
+///
+/// Scalars print plainly (`localhost`, `5432`, `true`, `null`). Arrays of scalars print
+/// comma-joined and objects with scalar values print as `key=value` pairs in key order.
+/// Anything nested deeper prints as compact JSON.
+///
+/// # Example
+/// ```
+/// use spicex::ConfigValue;
+/// use std::collections::HashMap;
+///
+/// assert_eq!(ConfigValue::from("localhost").to_string(), "localhost");
+/// assert_eq!(ConfigValue::from(vec![ConfigValue::from(1i64), ConfigValue::from(2i64)]).to_string(), "1, 2");
+///
+/// let mut obj = HashMap::new();
+/// obj.insert("port".to_string(), ConfigValue::from(5432i64));
+/// obj.insert("host".to_string(), ConfigValue::from("db"));
+/// assert_eq!(ConfigValue::Object(obj).to_string(), "host=db, port=5432");
+/// ```
+impl std::fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigValue::String(s) => f.write_str(s),
+            ConfigValue::Integer(i) => write!(f, "{i}"),
+            ConfigValue::Float(x) => write!(f, "{x}"),
+            ConfigValue::Boolean(b) => write!(f, "{b}"),
+            ConfigValue::Null => f.write_str("null"),
+            ConfigValue::Array(arr) if arr.iter().all(ConfigValue::is_scalar) => {
+                for (i, element) in arr.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                Ok(())
+            }
+            ConfigValue::Object(obj) if obj.values().all(ConfigValue::is_scalar) => {
+                let mut entries: Vec<_> = obj.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}={value}")?;
+                }
+                Ok(())
+            }
+            complex => write!(f, "{}", crate::parser::config_value_to_json(complex)),
+        }
+    }
 }
 
 impl From<String> for ConfigValue {
@@ -186,6 +256,36 @@ impl From<f32> for ConfigValue {
     }
 }
 
+/// Converts JSON data, such as a parsed HTTP response, without a round trip through text.
+///
+/// # Example
+/// ```
+/// use spicex::{ConfigValue, Spice};
+///
+/// let response = serde_json::json!({"region": "eu-west-1", "replicas": 3});
+/// let mut spice = Spice::new();
+/// spice.set("deploy", response.into()).unwrap();
+/// assert_eq!(spice.get_int("deploy.replicas").unwrap(), Some(3));
+/// ```
+impl From<serde_json::Value> for ConfigValue {
+    fn from(value: serde_json::Value) -> Self {
+        crate::parser::json_to_config_value(value)
+    }
+}
+
+impl From<serde_yaml::Value> for ConfigValue {
+    fn from(value: serde_yaml::Value) -> Self {
+        crate::parser::yaml_to_config_value(value)
+    }
+}
+
+/// TOML datetimes become strings, as they do when a TOML file is loaded.
+impl From<toml::Value> for ConfigValue {
+    fn from(value: toml::Value) -> Self {
+        crate::parser::toml_to_config_value(value)
+    }
+}
+
 impl From<Option<ConfigValue>> for ConfigValue {
     fn from(opt: Option<ConfigValue>) -> Self {
         opt.unwrap_or(ConfigValue::Null)
@@ -212,17 +312,23 @@ impl std::fmt::Display for ConversionError {
 
 impl std::error::Error for ConversionError {}
 
+impl ConversionError {
+    fn new(value: &ConfigValue, to_type: &str) -> Self {
+        Self {
+            from_type: value.type_name().to_string(),
+            to_type: to_type.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
 impl TryFrom<ConfigValue> for String {
     type Error = ConversionError;
 
     fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
         match value {
             ConfigValue::String(s) => Ok(s),
-            _ => Err(ConversionError {
-                from_type: value.type_name().to_string(),
-                to_type: "String".to_string(),
-                value: value.coerce_to_string(),
-            }),
+            _ => Err(ConversionError::new(&value, "String")),
         }
     }
 }
@@ -233,11 +339,7 @@ impl TryFrom<ConfigValue> for i64 {
     fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
         match value {
             ConfigValue::Integer(i) => Ok(i),
-            _ => Err(ConversionError {
-                from_type: value.type_name().to_string(),
-                to_type: "i64".to_string(),
-                value: value.coerce_to_string(),
-            }),
+            _ => Err(ConversionError::new(&value, "i64")),
         }
     }
 }
@@ -249,11 +351,7 @@ impl TryFrom<ConfigValue> for f64 {
         match value {
             ConfigValue::Float(f) => Ok(f),
             ConfigValue::Integer(i) => Ok(i as f64),
-            _ => Err(ConversionError {
-                from_type: value.type_name().to_string(),
-                to_type: "f64".to_string(),
-                value: value.coerce_to_string(),
-            }),
+            _ => Err(ConversionError::new(&value, "f64")),
         }
     }
 }
@@ -261,15 +359,53 @@ impl TryFrom<ConfigValue> for f64 {
 impl TryFrom<ConfigValue> for bool {
     type Error = ConversionError;
 
+    /// Converts like `ConfigValue::coerce_to_bool`, so `"yes"` and `1` are `true`.
     fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
-        match value {
-            ConfigValue::Boolean(b) => Ok(b),
-            _ => Err(ConversionError {
-                from_type: value.type_name().to_string(),
-                to_type: "bool".to_string(),
-                value: value.coerce_to_string(),
-            }),
-        }
+        value
+            .coerce_to_bool()
+            .ok_or_else(|| ConversionError::new(&value, "bool"))
+    }
+}
+
+impl TryFrom<ConfigValue> for i32 {
+    type Error = ConversionError;
+
+    fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
+        value
+            .as_i64()
+            .and_then(|i| i32::try_from(i).ok())
+            .ok_or_else(|| ConversionError::new(&value, "i32"))
+    }
+}
+
+impl TryFrom<ConfigValue> for u16 {
+    type Error = ConversionError;
+
+    fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
+        value
+            .as_i64()
+            .and_then(|i| u16::try_from(i).ok())
+            .ok_or_else(|| ConversionError::new(&value, "u16"))
+    }
+}
+
+impl TryFrom<ConfigValue> for Vec<String> {
+    type Error = ConversionError;
+
+    /// Converts an array whose elements are scalars, in their string form.
+    fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
+        let Some(elements) = value.as_array() else {
+            return Err(ConversionError::new(&value, "Vec<String>"));
+        };
+        elements
+            .iter()
+            .map(|element| match element {
+                ConfigValue::Array(_) | ConfigValue::Object(_) => {
+                    Err(ConversionError::new(&value, "Vec<String>"))
+                }
+                _ => Ok(element.coerce_to_string()),
+            })
+            .collect()
     }
 }
 
@@ -279,11 +415,7 @@ impl TryFrom<ConfigValue> for Vec<ConfigValue> {
     fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
         match value {
             ConfigValue::Array(arr) => Ok(arr),
-            _ => Err(ConversionError {
-                from_type: value.type_name().to_string(),
-                to_type: "Vec<ConfigValue>".to_string(),
-                value: value.coerce_to_string(),
-            }),
+            _ => Err(ConversionError::new(&value, "Vec<ConfigValue>")),
         }
     }
 }
@@ -294,16 +426,93 @@ impl TryFrom<ConfigValue> for HashMap<String, ConfigValue> {
     fn try_from(value: ConfigValue) -> Result<Self, Self::Error> {
         match value {
             ConfigValue::Object(obj) => Ok(obj),
-            _ => Err(ConversionError {
-                from_type: value.type_name().to_string(),
-                to_type: "HashMap<String, ConfigValue>".to_string(),
-                value: value.coerce_to_string(),
-            }),
+            _ => Err(ConversionError::new(&value, "HashMap<String, ConfigValue>")),
         }
     }
 }
 
+/// Constructs a [`ConfigValue`] from a JSON-like literal.
+///
+/// Objects, arrays and `null` are written inline; any other value is an
+/// expression converted with `ConfigValue::from`. Trailing commas are allowed.
+///
+/// # Example
+///
+/// ```rust
+/// use spicex::{config_value, ConfigValue};
+///
+/// let port = 5432;
+/// let value = config_value!({
+///     "database": {
+///         "host": "localhost",
+///         "port": port,
+///         "replicas": ["db1", "db2"],
+///         "password": null,
+///     },
+///     "debug": true,
+/// });
+///
+/// let database = value.as_object().unwrap()["database"].as_object().unwrap();
+/// assert_eq!(database["port"], ConfigValue::Integer(5432));
+/// assert_eq!(database["password"], ConfigValue::Null);
+/// ```
+#[macro_export]
+macro_rules! config_value {
+    (null) => {
+        $crate::ConfigValue::Null
+    };
+    ([ $($elements:tt)* ]) => {
+        $crate::ConfigValue::Array($crate::config_value!(@array [] $($elements)*))
+    };
+    ({ $($members:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut object = ::std::collections::HashMap::new();
+        $crate::config_value!(@object object $($members)*);
+        $crate::ConfigValue::Object(object)
+    }};
+    ($other:expr) => {
+        $crate::ConfigValue::from($other)
+    };
+
+    // Array elements, accumulated in order until the input is exhausted.
+    (@array [$($done:expr,)*]) => {
+        ::std::vec![$($done,)*]
+    };
+    (@array [$($done:expr,)*] null $(, $($rest:tt)*)?) => {
+        $crate::config_value!(@array [$($done,)* $crate::ConfigValue::Null,] $($($rest)*)?)
+    };
+    (@array [$($done:expr,)*] [ $($array:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::config_value!(@array [$($done,)* $crate::config_value!([ $($array)* ]),] $($($rest)*)?)
+    };
+    (@array [$($done:expr,)*] { $($map:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::config_value!(@array [$($done,)* $crate::config_value!({ $($map)* }),] $($($rest)*)?)
+    };
+    (@array [$($done:expr,)*] $next:expr $(, $($rest:tt)*)?) => {
+        $crate::config_value!(@array [$($done,)* $crate::config_value!($next),] $($($rest)*)?)
+    };
+
+    // Object members, inserted one `key: value` pair at a time.
+    (@object $object:ident) => {};
+    (@object $object:ident $key:literal : null $(, $($rest:tt)*)?) => {
+        $object.insert(::std::string::String::from($key), $crate::ConfigValue::Null);
+        $crate::config_value!(@object $object $($($rest)*)?);
+    };
+    (@object $object:ident $key:literal : [ $($array:tt)* ] $(, $($rest:tt)*)?) => {
+        $object.insert(::std::string::String::from($key), $crate::config_value!([ $($array)* ]));
+        $crate::config_value!(@object $object $($($rest)*)?);
+    };
+    (@object $object:ident $key:literal : { $($map:tt)* } $(, $($rest:tt)*)?) => {
+        $object.insert(::std::string::String::from($key), $crate::config_value!({ $($map)* }));
+        $crate::config_value!(@object $object $($($rest)*)?);
+    };
+    (@object $object:ident $key:literal : $value:expr $(, $($rest:tt)*)?) => {
+        $object.insert(::std::string::String::from($key), $crate::config_value!($value));
+        $crate::config_value!(@object $object $($($rest)*)?);
+    };
+}
+
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
 
@@ -319,6 +528,36 @@ mod tests {
         assert_eq!(bool_val.as_bool(), Some(true));
     }
 
+    #[test]
+    fn test_conversions_from_serde_values() {
+        let json = serde_json::json!({"name": "app", "ports": [80, 443], "ratio": 0.5});
+        let value = ConfigValue::from(json.clone());
+        let obj = value.as_object().unwrap();
+        assert_eq!(obj["name"], ConfigValue::from("app"));
+        assert_eq!(
+            obj["ports"],
+            ConfigValue::Array(vec![ConfigValue::Integer(80), ConfigValue::Integer(443)])
+        );
+        assert_eq!(value.into_json(), json);
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str("debug: true\nlevel: 3\n").unwrap();
+        let value = ConfigValue::from(yaml);
+        assert_eq!(
+            value.as_object().unwrap()["debug"],
+            ConfigValue::Boolean(true)
+        );
+
+        let toml: toml::Value = toml::from_str("[server]\nhost = \"localhost\"\n").unwrap();
+        let value = ConfigValue::from(toml);
+        let server = value.as_object().unwrap()["server"].as_object().unwrap();
+        assert_eq!(server["host"], ConfigValue::from("localhost"));
+
+        assert_eq!(
+            ConfigValue::Float(f64::NAN).into_json(),
+            serde_json::Value::Null
+        );
+    }
+
     #[test]
     fn test_type_checking() {
         let null_val = ConfigValue::Null;
@@ -361,6 +600,33 @@ mod tests {
         assert_eq!(obj_val.coerce_to_string(), "[object]");
     }
 
+    #[test]
+    fn test_display() {
+        assert_eq!(ConfigValue::from("localhost").to_string(), "localhost");
+        assert_eq!(ConfigValue::from(5432i64).to_string(), "5432");
+        assert_eq!(ConfigValue::from(2.5).to_string(), "2.5");
+        assert_eq!(ConfigValue::from(true).to_string(), "true");
+        assert_eq!(ConfigValue::Null.to_string(), "null");
+        assert_eq!(
+            ConfigValue::from(vec![ConfigValue::from("a"), ConfigValue::from(1i64)]).to_string(),
+            "a, 1"
+        );
+
+        let mut obj = HashMap::new();
+        obj.insert("port".to_string(), ConfigValue::from(5432i64));
+        obj.insert("host".to_string(), ConfigValue::from("db"));
+        assert_eq!(
+            ConfigValue::Object(obj.clone()).to_string(),
+            "host=db, port=5432"
+        );
+
+        let nested = ConfigValue::from(vec![ConfigValue::Object(obj), ConfigValue::Array(vec![])]);
+        assert_eq!(nested.to_string(), r#"[{"host":"db","port":5432},[]]"#);
+
+        let error = i64::try_from(ConfigValue::from(vec![ConfigValue::from(1i64)])).unwrap_err();
+        assert_eq!(error.to_string(), "Cannot convert Array value '1' to i64");
+    }
+
     #[test]
     fn test_coerce_to_bool() {
         // Test boolean values
@@ -509,7 +775,7 @@ mod tests {
 
         let bool_val = ConfigValue::Boolean(true);
         let converted_bool: bool = bool_val.try_into().unwrap();
-        assert_eq!(converted_bool, true);
+        assert!(converted_bool);
 
         let array_val = ConfigValue::Array(vec![ConfigValue::Integer(1)]);
         let converted_array: Vec<ConfigValue> = array_val.try_into().unwrap();
@@ -540,6 +806,45 @@ mod tests {
         let bool_val = ConfigValue::Boolean(true);
         let float_result: Result<f64, ConversionError> = bool_val.try_into();
         assert!(float_result.is_err());
+
+        let string_val = ConfigValue::String("maybe".to_string());
+        let bool_result: Result<bool, ConversionError> = string_val.try_into();
+        assert!(bool_result.is_err());
+    }
+
+    #[test]
+    fn test_try_from_narrow_integers_and_strings() {
+        let port: u16 = ConfigValue::Integer(8080).try_into().unwrap();
+        assert_eq!(port, 8080);
+        let offset: i32 = ConfigValue::Integer(-5).try_into().unwrap();
+        assert_eq!(offset, -5);
+        let enabled: bool = ConfigValue::String("yes".to_string()).try_into().unwrap();
+        assert!(enabled);
+        let hosts: Vec<String> =
+            ConfigValue::Array(vec![ConfigValue::from("a.com"), ConfigValue::Integer(1)])
+                .try_into()
+                .unwrap();
+        assert_eq!(hosts, vec!["a.com".to_string(), "1".to_string()]);
+
+        let err = u16::try_from(ConfigValue::Integer(70000)).unwrap_err();
+        assert_eq!(err.to_type, "u16");
+        assert_eq!(err.value, "70000");
+        assert!(u16::try_from(ConfigValue::Integer(-1)).is_err());
+        assert!(i32::try_from(ConfigValue::Integer(i64::MAX)).is_err());
+        assert!(i32::try_from(ConfigValue::Float(1.5)).is_err());
+        assert!(Vec::<String>::try_from(ConfigValue::from("a.com")).is_err());
+        assert!(
+            Vec::<String>::try_from(ConfigValue::Array(vec![ConfigValue::Array(vec![])])).is_err()
+        );
+
+        // Inside functions returning ConfigResult, `?` yields a type conversion error
+        fn read_port(value: ConfigValue) -> crate::error::ConfigResult<u16> {
+            Ok(value.try_into()?)
+        }
+        assert_eq!(read_port(ConfigValue::Integer(443)).unwrap(), 443);
+        assert!(read_port(ConfigValue::from("https"))
+            .unwrap_err()
+            .is_type_conversion());
     }
 
     #[test]
@@ -600,4 +905,76 @@ mod tests {
         let deserialized: ConfigValue = serde_json::from_str("{\"key\": \"value\"}").unwrap();
         assert!(matches!(deserialized, ConfigValue::Object(_)));
     }
+
+    #[test]
+    fn test_config_value_macro_scalars() {
+        assert_eq!(config_value!(null), ConfigValue::Null);
+        assert_eq!(
+            config_value!("text"),
+            ConfigValue::String("text".to_string())
+        );
+        assert_eq!(config_value!(42), ConfigValue::Integer(42));
+        assert_eq!(config_value!(-7), ConfigValue::Integer(-7));
+        assert_eq!(config_value!(2.5), ConfigValue::Float(2.5));
+        assert_eq!(config_value!(true), ConfigValue::Boolean(true));
+
+        let name = String::from("spice");
+        assert_eq!(
+            config_value!(name),
+            ConfigValue::String("spice".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_value_macro_arrays() {
+        assert_eq!(config_value!([]), ConfigValue::Array(vec![]));
+        assert_eq!(
+            config_value!([1, "two", 3.0, false, null, [4], { "five": 5 },]),
+            ConfigValue::Array(vec![
+                ConfigValue::Integer(1),
+                ConfigValue::String("two".to_string()),
+                ConfigValue::Float(3.0),
+                ConfigValue::Boolean(false),
+                ConfigValue::Null,
+                ConfigValue::Array(vec![ConfigValue::Integer(4)]),
+                ConfigValue::Object(HashMap::from([(
+                    "five".to_string(),
+                    ConfigValue::Integer(5)
+                )])),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_config_value_macro_nested_objects() {
+        let port = 5432;
+        let value = config_value!({
+            "database": {
+                "host": "localhost",
+                "port": port,
+                "ssl": true,
+                "timeout": 1.5,
+                "replicas": ["db1", "db2"],
+                "password": null
+            },
+            "empty": {}
+        });
+
+        let mut database = HashMap::new();
+        database.insert("host".to_string(), ConfigValue::from("localhost"));
+        database.insert("port".to_string(), ConfigValue::Integer(5432));
+        database.insert("ssl".to_string(), ConfigValue::Boolean(true));
+        database.insert("timeout".to_string(), ConfigValue::Float(1.5));
+        database.insert(
+            "replicas".to_string(),
+            ConfigValue::Array(vec![ConfigValue::from("db1"), ConfigValue::from("db2")]),
+        );
+        database.insert("password".to_string(), ConfigValue::Null);
+
+        let mut expected = HashMap::new();
+        expected.insert("database".to_string(), ConfigValue::Object(database));
+        expected.insert("empty".to_string(), ConfigValue::Object(HashMap::new()));
+
+        assert_eq!(value, ConfigValue::Object(expected));
+    }
 }
@@ -1,22 +1,310 @@
 //! File system watching utilities for configuration files.
 
 use crate::error::{ConfigError, ConfigResult};
-use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 /// Type alias for configuration change callback functions.
 pub type ConfigChangeCallback = Box<dyn Fn() + Send + Sync>;
 
+/// File name patterns of editor swap, backup and temporary files, which never count as
+/// changes to a watched file.
+const DEFAULT_IGNORE_PATTERNS: &[&str] = &["*.swp", "*.swx", "*~", "*.tmp", ".#*", "4913"];
+
+/// Modification time and size of a file, or None if it does not exist.
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+/// Last known state of each watched file, shared with the background thread.
+type WatchStates = Arc<Mutex<HashMap<PathBuf, WatchState>>>;
+
+/// Health of a watched configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchState {
+    /// The file exists and changes to it are detected
+    Active,
+    /// The file does not exist; with polling, its creation is detected
+    Missing,
+    /// Watching the file failed, so changes to it go unnoticed
+    Error(String),
+}
+
+/// A change detected in one or more watched configuration files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// The configuration files that changed
+    pub paths: Vec<PathBuf>,
+}
+
+/// Asynchronous stream of configuration file changes.
+///
+/// Created by `Spice::watch_config_stream`, together with a `WatchHandle` that cancels it.
+/// Changes are delivered by the file system notifier directly into a tokio channel, so no
+/// extra thread is spawned. The stream ends once it is cancelled; dropping it also stops
+/// watching.
+#[cfg(feature = "tokio")]
+pub struct ConfigChangeStream {
+    receiver: tokio::sync::mpsc::UnboundedReceiver<ConfigChange>,
+    handle: WatchHandle,
+}
+
+/// Cancels the watch behind a `ConfigChangeStream`.
+///
+/// Handles are cheap to clone and can be moved to other tasks or threads. Cancelling
+/// releases the file system watcher and ends the stream, even while a task is waiting on it.
+#[cfg(feature = "tokio")]
+#[derive(Clone)]
+pub struct WatchHandle {
+    /// The notifier, taken out and dropped on cancellation
+    watcher: Arc<Mutex<Option<RecommendedWatcher>>>,
+}
+
+#[cfg(feature = "tokio")]
+impl WatchHandle {
+    /// Stops watching and ends the stream. Changes not yet received are discarded.
+    /// Cancelling more than once has no further effect.
+    pub fn cancel(&self) {
+        let watcher = self
+            .watcher
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        // Dropping the notifier drops its channel sender, which wakes a waiting stream
+        drop(watcher);
+    }
+
+    /// Returns whether the watch has been cancelled, explicitly or by dropping the stream.
+    pub fn is_cancelled(&self) -> bool {
+        self.watcher
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .is_none()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl std::fmt::Debug for WatchHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WatchHandle")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl ConfigChangeStream {
+    /// Creates a stream reporting changes to the given files.
+    ///
+    /// # Arguments
+    /// * `paths` - The configuration files to watch
+    /// * `ignore_patterns` - File name patterns whose events are ignored
+    /// * `on_change` - Called from the notifier before each change is delivered
+    pub(crate) fn new<F>(
+        paths: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+        on_change: F,
+    ) -> ConfigResult<Self>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let watched = paths.clone();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            // Reads of the configuration files (including our own reloads) are not changes
+            if event.kind.is_access() {
+                return;
+            }
+
+            let changed: Vec<PathBuf> = watched
+                .iter()
+                .filter(|path| {
+                    event
+                        .paths
+                        .iter()
+                        .any(|p| !is_ignored(p, &ignore_patterns) && is_same_file(p, path))
+                })
+                .cloned()
+                .collect();
+            if changed.is_empty() {
+                return;
+            }
+
+            on_change();
+            // The receiver may already be gone; the watcher is dropped along with it
+            let _ = sender.send(ConfigChange { paths: changed });
+        })
+        .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+
+        for dir in watched_dirs(&paths) {
+            watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+        }
+
+        Ok(Self {
+            receiver,
+            handle: WatchHandle {
+                watcher: Arc::new(Mutex::new(Some(watcher))),
+            },
+        })
+    }
+
+    /// Returns a handle that cancels this stream.
+    pub(crate) fn handle(&self) -> WatchHandle {
+        self.handle.clone()
+    }
+
+    /// Waits for the next configuration change.
+    ///
+    /// # Returns
+    /// * `Option<ConfigChange>` - The next change, or None once watching has been cancelled
+    pub async fn next(&mut self) -> Option<ConfigChange> {
+        std::future::poll_fn(|cx| self.poll_change(cx)).await
+    }
+
+    /// Polls for the next change, ending the stream once the watch is cancelled.
+    fn poll_change(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<ConfigChange>> {
+        if self.handle.is_cancelled() {
+            return std::task::Poll::Ready(None);
+        }
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for ConfigChangeStream {
+    fn drop(&mut self) {
+        self.handle.cancel();
+    }
+}
+
+/// Returns true if an event path refers to a watched file.
+/// Event paths may be canonicalized differently (e.g. through symlinked directories),
+/// so a path with the same file name in the same directory, once resolved, also matches.
+/// Siblings such as `config.yaml.swp` never match `config.yaml`.
+fn is_same_file(event_path: &Path, watched: &Path) -> bool {
+    if event_path.ends_with(watched) {
+        return true;
+    }
+    event_path.file_name() == watched.file_name()
+        && match (
+            event_path.parent(),
+            std::fs::canonicalize(watch_dir(watched)),
+        ) {
+            (Some(parent), Ok(dir)) => std::fs::canonicalize(parent).is_ok_and(|p| p == dir),
+            _ => false,
+        }
+}
+
+/// Returns true if the file name of `path` matches one of the default or given ignore
+/// patterns.
+fn is_ignored(path: &Path, patterns: &[String]) -> bool {
+    let Some(name) = path.file_name() else {
+        return false;
+    };
+    let name: Vec<char> = name.to_string_lossy().chars().collect();
+    DEFAULT_IGNORE_PATTERNS
+        .iter()
+        .copied()
+        .chain(patterns.iter().map(String::as_str))
+        .any(|pattern| glob_matches(&pattern.chars().collect::<Vec<_>>(), &name))
+}
+
+/// Matches a file name against a pattern where `*` matches any run of characters and `?`
+/// matches a single character.
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| glob_matches(rest, &name[skip..])),
+        Some((&c, rest)) => match name.split_first() {
+            Some((&n, name_rest)) if c == '?' || c == n => glob_matches(rest, name_rest),
+            _ => false,
+        },
+    }
+}
+
+/// Returns the directory watched for changes to `file`.
+/// Editors commonly save by renaming a new file over the old one. A watch on the file
+/// itself follows the replaced inode and goes silent after the first such save, so the
+/// parent directory is watched instead and its events are filtered by file name.
+fn watch_dir(file: &Path) -> PathBuf {
+    match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// Returns the distinct directories watched for `files`, in first-seen order.
+fn watched_dirs(files: &[PathBuf]) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for dir in files.iter().map(|file| watch_dir(file)) {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Returns true if a notifier event changes one of the watched files. Reads are not
+/// changes, and neither are events for other files in a watched directory or for files
+/// matching an ignore pattern.
+fn is_relevant(
+    event: &notify::Result<Event>,
+    files: &[PathBuf],
+    ignore_patterns: &[String],
+) -> bool {
+    match event {
+        Ok(event) => {
+            !event.kind.is_access()
+                && event.paths.iter().any(|path| {
+                    !is_ignored(path, ignore_patterns)
+                        && files.iter().any(|file| is_same_file(path, file))
+                })
+        }
+        Err(_) => true,
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl futures_core::Stream for ConfigChangeStream {
+    type Item = ConfigChange;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.poll_change(cx)
+    }
+}
+
 /// Manages file system watching for configuration files.
 pub struct FileWatcher {
-    _watcher: RecommendedWatcher,
-    receiver: mpsc::Receiver<notify::Result<Event>>,
+    /// Native OS watcher; dropped once the watcher switches to polling
+    watcher: Option<RecommendedWatcher>,
+    receiver: Mutex<mpsc::Receiver<notify::Result<Event>>>,
     watched_files: Vec<PathBuf>,
+    /// Last known state of each watched file
+    states: WatchStates,
     callbacks: Arc<Mutex<Vec<ConfigChangeCallback>>>,
     is_watching: bool,
+    /// Sender used to signal the background thread to shut down
+    stop_sender: Option<mpsc::Sender<()>>,
+    /// Handle of the background watching thread, joined on shutdown
+    worker: Option<thread::JoinHandle<()>>,
+    /// Interval for stat-based polling; native OS notifications are used when None
+    poll_interval: Option<Duration>,
+    /// File name patterns whose events are ignored, besides the editor defaults
+    ignore_patterns: Vec<String>,
 }
 
 impl FileWatcher {
@@ -29,15 +317,20 @@ impl FileWatcher {
 
         let path_buf = path.as_ref().to_path_buf();
         watcher
-            .watch(&path_buf, RecursiveMode::NonRecursive)
+            .watch(&watch_dir(&path_buf), RecursiveMode::NonRecursive)
             .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
 
         Ok(Self {
-            _watcher: watcher,
-            receiver,
+            watcher: Some(watcher),
+            receiver: Mutex::new(receiver),
             watched_files: vec![path_buf],
+            states: Arc::new(Mutex::new(HashMap::new())),
             callbacks: Arc::new(Mutex::new(Vec::new())),
             is_watching: false,
+            stop_sender: None,
+            worker: None,
+            poll_interval: None,
+            ignore_patterns: Vec::new(),
         })
     }
 
@@ -49,18 +342,65 @@ impl FileWatcher {
             .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
 
         Ok(Self {
-            _watcher: watcher,
-            receiver,
+            watcher: Some(watcher),
+            receiver: Mutex::new(receiver),
             watched_files: Vec::new(),
+            states: Arc::new(Mutex::new(HashMap::new())),
             callbacks: Arc::new(Mutex::new(Vec::new())),
             is_watching: false,
+            stop_sender: None,
+            worker: None,
+            poll_interval: None,
+            ignore_patterns: Vec::new(),
         })
     }
 
+    /// Switches the watcher to stat-based polling at the given interval.
+    /// Polling compares each file's modification time and size instead of relying on
+    /// native OS notifications, which are unreliable on network filesystems. It detects
+    /// modification, deletion, and creation of a previously missing file. If the watcher
+    /// is running it is restarted in polling mode.
+    pub fn set_poll_interval(&mut self, interval: Duration) -> ConfigResult<()> {
+        self.poll_interval = Some(interval);
+        self.watcher = None;
+        if self.is_watching {
+            self.stop_watching();
+            self.start_watching()?;
+        }
+        Ok(())
+    }
+
+    /// Gets the polling interval, or None if native notifications are used.
+    pub fn poll_interval(&self) -> Option<Duration> {
+        self.poll_interval
+    }
+
+    /// Ignores events for files whose name matches `pattern`, where `*` matches any run of
+    /// characters and `?` a single character. Only events for the watched files themselves
+    /// count as changes, and swap, backup and temporary files of common editors (such as
+    /// `config.yaml.swp`, `config.yaml~` and `.config.yaml.tmp`) are always ignored; this
+    /// covers other project-specific files. If the watcher is running it is restarted.
+    pub fn add_ignore_pattern(&mut self, pattern: &str) -> ConfigResult<()> {
+        self.ignore_patterns.push(pattern.to_string());
+        if self.is_watching {
+            self.stop_watching();
+            self.start_watching()?;
+        }
+        Ok(())
+    }
+
     /// Adds a file to be watched.
+    /// In polling mode the file does not need to exist yet; its creation is reported
+    /// as a change. A running polling watcher is restarted so the file is polled too.
     pub fn watch_file<P: AsRef<Path>>(&mut self, path: P) -> ConfigResult<()> {
         let path_buf = path.as_ref().to_path_buf();
 
+        if let Some(interval) = self.poll_interval {
+            self.watched_files.push(path_buf);
+            self.restart_polling(interval);
+            return Ok(());
+        }
+
         // Only watch if the file exists
         if !path_buf.exists() {
             return Err(ConfigError::FileWatch(format!(
@@ -69,23 +409,37 @@ impl FileWatcher {
             )));
         }
 
-        self._watcher
-            .watch(&path_buf, RecursiveMode::NonRecursive)
-            .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+        if let Some(watcher) = self.watcher.as_mut() {
+            watcher
+                .watch(&watch_dir(&path_buf), RecursiveMode::NonRecursive)
+                .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+        }
 
         self.watched_files.push(path_buf);
         Ok(())
     }
 
     /// Removes a file from being watched.
+    /// A running polling watcher is restarted so the file is no longer polled.
     pub fn unwatch_file<P: AsRef<Path>>(&mut self, path: P) -> ConfigResult<()> {
         let path_buf = path.as_ref().to_path_buf();
+        lock_states(&self.states).remove(&path_buf);
+        self.watched_files.retain(|p| p != &path_buf);
 
-        self._watcher
-            .unwatch(&path_buf)
-            .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+        if let Some(interval) = self.poll_interval {
+            self.restart_polling(interval);
+            return Ok(());
+        }
 
-        self.watched_files.retain(|p| p != &path_buf);
+        // The directory stays watched while other watched files live in it
+        let dir = watch_dir(&path_buf);
+        if !self.watched_files.iter().any(|p| watch_dir(p) == dir) {
+            if let Some(watcher) = self.watcher.as_mut() {
+                watcher
+                    .unwatch(&dir)
+                    .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+            }
+        }
         Ok(())
     }
 
@@ -94,14 +448,36 @@ impl FileWatcher {
         &self.watched_files
     }
 
+    /// Reports the health of each watched file, so a broken watcher can be told apart from
+    /// one that simply has seen no changes. A file that is missing, or that could not be
+    /// watched, is reported as such even while `is_watching` is true.
+    ///
+    /// # Returns
+    /// * `Vec<(PathBuf, WatchState)>` - Each watched file and its state
+    pub fn watch_status(&self) -> Vec<(PathBuf, WatchState)> {
+        let states = lock_states(&self.states);
+        self.watched_files
+            .iter()
+            .map(|path| {
+                let state = match states.get(path) {
+                    Some(WatchState::Error(message)) => WatchState::Error(message.clone()),
+                    _ if !path.exists() => WatchState::Missing,
+                    _ => WatchState::Active,
+                };
+                (path.clone(), state)
+            })
+            .collect()
+    }
+
     /// Registers a callback to be called when configuration changes are detected.
     pub fn on_config_change<F>(&self, callback: F) -> ConfigResult<()>
     where
         F: Fn() + Send + Sync + 'static,
     {
-        let mut callbacks = self.callbacks.lock().map_err(|e| {
-            ConfigError::FileWatch(format!("Failed to acquire callback lock: {e}"))
-        })?;
+        let mut callbacks = self
+            .callbacks
+            .lock()
+            .map_err(|e| ConfigError::FileWatch(format!("Failed to acquire callback lock: {e}")))?;
 
         callbacks.push(Box::new(callback));
         Ok(())
@@ -110,13 +486,22 @@ impl FileWatcher {
     /// Starts watching for file changes in a background thread.
     /// This method spawns a background thread that monitors for file changes
     /// and calls registered callbacks when changes are detected.
+    ///
+    /// # Errors
+    /// * `ConfigError::FileWatch` - If none of the watched files could be watched. When only
+    ///   some fail, watching starts and `watch_status` reports the failures.
     pub fn start_watching(&mut self) -> ConfigResult<()> {
         if self.is_watching {
             return Ok(()); // Already watching
         }
 
+        if let Some(interval) = self.poll_interval {
+            self.start_polling(interval);
+            return Ok(());
+        }
+
         let callbacks = Arc::clone(&self.callbacks);
-        let (_stop_sender, stop_receiver) = mpsc::channel::<()>();
+        let (stop_sender, stop_receiver) = mpsc::channel::<()>();
 
         // We need to create a new receiver since we can't clone the existing one
         let (event_sender, event_receiver) = mpsc::channel();
@@ -125,26 +510,52 @@ impl FileWatcher {
         let mut new_watcher = notify::recommended_watcher(event_sender)
             .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
 
-        // Re-watch all previously watched files
-        for path in &self.watched_files {
-            new_watcher
-                .watch(path, RecursiveMode::NonRecursive)
-                .map_err(|e| ConfigError::FileWatch(e.to_string()))?;
+        // Re-watch the directories of all watched files. A directory that fails is reported
+        // by watch_status, unless none could be watched at all.
+        let dirs = watched_dirs(&self.watched_files);
+        let mut failures = Vec::new();
+        for dir in &dirs {
+            if let Err(e) = new_watcher.watch(dir, RecursiveMode::NonRecursive) {
+                failures.push((dir.clone(), e.to_string()));
+            }
+        }
+        if !dirs.is_empty() && failures.len() == dirs.len() {
+            return Err(ConfigError::FileWatch(failures.swap_remove(0).1));
+        }
+
+        let mut states = lock_states(&self.states);
+        states.clear();
+        for (dir, message) in failures {
+            for path in self.watched_files.iter().filter(|p| watch_dir(p) == dir) {
+                states.insert(path.clone(), WatchState::Error(message.clone()));
+            }
         }
+        drop(states);
 
-        self._watcher = new_watcher;
+        self.watcher = Some(new_watcher);
         self.is_watching = true;
+        self.stop_sender = Some(stop_sender);
+        let states = Arc::clone(&self.states);
+        let files = self.watched_files.clone();
+        let ignore_patterns = self.ignore_patterns.clone();
 
         // Spawn background thread for watching
-        thread::spawn(move || {
+        let worker = thread::spawn(move || {
             loop {
-                // Check if we should stop
-                if stop_receiver.try_recv().is_ok() {
+                // Check if we should stop (an explicit signal or a dropped sender)
+                if !matches!(stop_receiver.try_recv(), Err(mpsc::TryRecvError::Empty)) {
                     break;
                 }
 
                 // Check for file system events
-                match event_receiver.recv_timeout(Duration::from_millis(100)) {
+                let event = event_receiver.recv_timeout(Duration::from_millis(100));
+                if let Ok(event) = &event {
+                    record_event(&states, &files, event);
+                    if !is_relevant(event, &files, &ignore_patterns) {
+                        continue;
+                    }
+                }
+                match event {
                     Ok(Ok(_event)) => {
                         // File change detected, call all callbacks
                         if let Ok(callbacks_guard) = callbacks.lock() {
@@ -168,15 +579,65 @@ impl FileWatcher {
                 }
             }
         });
+        self.worker = Some(worker);
 
         Ok(())
     }
 
+    /// Starts a background thread that polls the watched files at `interval` and calls
+    /// registered callbacks when any file's modification time, size, or existence changes.
+    fn start_polling(&mut self, interval: Duration) {
+        let callbacks = Arc::clone(&self.callbacks);
+        let (stop_sender, stop_receiver) = mpsc::channel::<()>();
+        let files = self.watched_files.clone();
+        let states = Arc::clone(&self.states);
+        // Snapshot before returning so changes made right after starting are not missed
+        let mut stamps: Vec<FileStamp> = files.iter().map(|path| file_stamp(path)).collect();
+        record_stamps(&states, &files, &stamps);
+
+        self.is_watching = true;
+        self.stop_sender = Some(stop_sender);
+
+        let worker = thread::spawn(move || {
+            // Waiting on the stop channel doubles as the poll delay
+            while let Err(mpsc::RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(interval) {
+                let current: Vec<FileStamp> = files.iter().map(|path| file_stamp(path)).collect();
+                if current != stamps {
+                    record_stamps(&states, &files, &current);
+                    stamps = current;
+                    if let Ok(callbacks_guard) = callbacks.lock() {
+                        for callback in callbacks_guard.iter() {
+                            callback();
+                        }
+                    }
+                }
+            }
+        });
+        self.worker = Some(worker);
+    }
+
+    /// Restarts a running polling thread so it picks up the current list of watched files.
+    fn restart_polling(&mut self, interval: Duration) {
+        if self.is_watching {
+            self.stop_watching();
+            self.start_polling(interval);
+        }
+    }
+
     /// Stops watching for file changes.
+    /// Signals the background thread to exit and waits for it to finish, so no
+    /// watcher thread outlives the call.
     pub fn stop_watching(&mut self) {
         self.is_watching = false;
-        // Note: In a full implementation, we'd send a stop signal to the background thread
-        // For now, the thread will detect disconnection and stop
+
+        if let Some(stop_sender) = self.stop_sender.take() {
+            // The thread may already have exited on disconnection; that's fine
+            let _ = stop_sender.send(());
+        }
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
     }
 
     /// Returns whether the watcher is currently active.
@@ -194,24 +655,40 @@ impl FileWatcher {
         }
     }
 
+    /// Locks the receiver of file system events. A panic while it was locked cannot leave
+    /// it inconsistent, so a poisoned lock is recovered.
+    fn lock_receiver(&self) -> std::sync::MutexGuard<'_, mpsc::Receiver<notify::Result<Event>>> {
+        self.receiver
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
     /// Checks for file system events with a timeout.
     /// This method is primarily for testing and manual polling.
     /// For automatic reloading, use start_watching() instead.
     pub fn check_for_changes(&self, timeout: Duration) -> ConfigResult<bool> {
-        match self.receiver.recv_timeout(timeout) {
-            Ok(Ok(_event)) => {
-                // Call callbacks when changes are detected
-                if let Ok(callbacks_guard) = self.callbacks.lock() {
-                    for callback in callbacks_guard.iter() {
-                        callback();
+        let deadline = std::time::Instant::now() + timeout;
+        let receiver = self.lock_receiver();
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            match receiver.recv_timeout(remaining) {
+                Ok(event) if !is_relevant(&event, &self.watched_files, &self.ignore_patterns) => {
+                    continue
+                }
+                Ok(Ok(_event)) => {
+                    // Call callbacks when changes are detected
+                    if let Ok(callbacks_guard) = self.callbacks.lock() {
+                        for callback in callbacks_guard.iter() {
+                            callback();
+                        }
                     }
+                    return Ok(true);
+                }
+                Ok(Err(e)) => return Err(ConfigError::FileWatch(e.to_string())),
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(false),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return Err(ConfigError::FileWatch("Watcher disconnected".to_string()))
                 }
-                Ok(true)
-            }
-            Ok(Err(e)) => Err(ConfigError::FileWatch(e.to_string())),
-            Err(mpsc::RecvTimeoutError::Timeout) => Ok(false),
-            Err(mpsc::RecvTimeoutError::Disconnected) => {
-                Err(ConfigError::FileWatch("Watcher disconnected".to_string()))
             }
         }
     }
@@ -220,22 +697,88 @@ impl FileWatcher {
     /// This method is primarily for testing and manual polling.
     /// For automatic reloading, use start_watching() instead.
     pub fn wait_for_change(&self) -> ConfigResult<()> {
-        match self.receiver.recv() {
-            Ok(Ok(_event)) => {
-                // Call callbacks when changes are detected
-                if let Ok(callbacks_guard) = self.callbacks.lock() {
-                    for callback in callbacks_guard.iter() {
-                        callback();
+        let receiver = self.lock_receiver();
+        loop {
+            match receiver.recv() {
+                Ok(event) if !is_relevant(&event, &self.watched_files, &self.ignore_patterns) => {
+                    continue
+                }
+                Ok(Ok(_event)) => {
+                    // Call callbacks when changes are detected
+                    if let Ok(callbacks_guard) = self.callbacks.lock() {
+                        for callback in callbacks_guard.iter() {
+                            callback();
+                        }
                     }
+                    return Ok(());
                 }
-                Ok(())
+                Ok(Err(e)) => return Err(ConfigError::FileWatch(e.to_string())),
+                Err(_) => return Err(ConfigError::FileWatch("Watcher disconnected".to_string())),
             }
-            Ok(Err(e)) => Err(ConfigError::FileWatch(e.to_string())),
-            Err(_) => Err(ConfigError::FileWatch("Watcher disconnected".to_string())),
         }
     }
 }
 
+/// Locks the watch states. The map is always left consistent, so a poisoned lock is
+/// recovered.
+fn lock_states(states: &WatchStates) -> std::sync::MutexGuard<'_, HashMap<PathBuf, WatchState>> {
+    states
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Updates the state of the watched files an event or notifier error refers to. An error
+/// that names no file applies to every watched file.
+fn record_event(states: &WatchStates, files: &[PathBuf], event: &notify::Result<Event>) {
+    let mut states = lock_states(states);
+    match event {
+        Ok(event) => {
+            let state = match event.kind {
+                EventKind::Remove(_) => WatchState::Missing,
+                EventKind::Create(_) | EventKind::Modify(_) => WatchState::Active,
+                _ => return,
+            };
+            for file in files {
+                if event.paths.iter().any(|path| is_same_file(path, file)) {
+                    states.insert(file.clone(), state.clone());
+                }
+            }
+        }
+        Err(e) => {
+            for file in files {
+                if e.paths.is_empty() || e.paths.iter().any(|path| is_same_file(path, file)) {
+                    states.insert(file.clone(), WatchState::Error(e.to_string()));
+                }
+            }
+        }
+    }
+}
+
+/// Updates the state of polled files from their latest stamps.
+fn record_stamps(states: &WatchStates, files: &[PathBuf], stamps: &[FileStamp]) {
+    let mut states = lock_states(states);
+    for (file, stamp) in files.iter().zip(stamps) {
+        let state = match stamp {
+            Some(_) => WatchState::Active,
+            None => WatchState::Missing,
+        };
+        states.insert(file.clone(), state);
+    }
+}
+
+/// Reads the modification time and size of a file for polling comparisons.
+fn file_stamp(path: &Path) -> FileStamp {
+    std::fs::metadata(path)
+        .ok()
+        .map(|metadata| (metadata.modified().ok(), metadata.len()))
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.stop_watching();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -410,6 +953,324 @@ mod tests {
         assert!(!watcher.is_watching());
     }
 
+    #[test]
+    fn test_stop_watching_joins_background_thread() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new(&config_path).unwrap();
+        watcher.start_watching().unwrap();
+        assert!(watcher.worker.is_some());
+
+        watcher.stop_watching();
+        assert!(watcher.worker.is_none());
+        assert!(watcher.stop_sender.is_none());
+
+        // Watching can be restarted after a clean shutdown
+        watcher.start_watching().unwrap();
+        assert!(watcher.is_watching());
+        let worker = watcher.worker.take().unwrap();
+
+        // Dropping the watcher signals the thread to exit
+        drop(watcher);
+        worker.join().unwrap();
+    }
+
+    /// Waits up to two seconds for the counter to reach `expected`.
+    fn wait_for_count(counter: &Arc<Mutex<usize>>, expected: usize) -> bool {
+        for _ in 0..100 {
+            if *counter.lock().unwrap() >= expected {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn test_polling_detects_modify_delete_and_create() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("config.json");
+        let missing = temp_dir.path().join("later.json");
+        fs::write(&existing, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new_empty().unwrap();
+        watcher
+            .set_poll_interval(Duration::from_millis(20))
+            .unwrap();
+        watcher.watch_file(&existing).unwrap();
+        // A missing file can be watched while polling
+        watcher.watch_file(&missing).unwrap();
+
+        let counter = Arc::new(Mutex::new(0));
+        let counter_clone = Arc::clone(&counter);
+        watcher
+            .on_config_change(move || *counter_clone.lock().unwrap() += 1)
+            .unwrap();
+        watcher.start_watching().unwrap();
+
+        // Modification (size change)
+        fs::write(&existing, r#"{"key": "value"}"#).unwrap();
+        assert!(wait_for_count(&counter, 1));
+
+        // Deletion
+        fs::remove_file(&existing).unwrap();
+        assert!(wait_for_count(&counter, 2));
+
+        // Creation of a previously missing file
+        fs::write(&missing, "{}").unwrap();
+        assert!(wait_for_count(&counter, 3));
+
+        watcher.stop_watching();
+        assert!(watcher.worker.is_none());
+    }
+
+    #[test]
+    fn test_set_poll_interval_restarts_running_watcher() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new(&config_path).unwrap();
+        watcher.start_watching().unwrap();
+        assert_eq!(watcher.poll_interval(), None);
+
+        watcher
+            .set_poll_interval(Duration::from_millis(20))
+            .unwrap();
+        assert!(watcher.is_watching());
+        assert_eq!(watcher.poll_interval(), Some(Duration::from_millis(20)));
+
+        let counter = Arc::new(Mutex::new(0));
+        let counter_clone = Arc::clone(&counter);
+        watcher
+            .on_config_change(move || *counter_clone.lock().unwrap() += 1)
+            .unwrap();
+        fs::write(&config_path, r#"{"changed": true}"#).unwrap();
+        assert!(wait_for_count(&counter, 1));
+        // The native watcher is not kept around while polling
+        assert!(watcher.watcher.is_none());
+    }
+
+    #[test]
+    fn test_polling_picks_up_files_added_after_start() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("config.json");
+        let added = temp_dir.path().join("override.json");
+        fs::write(&first, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new_empty().unwrap();
+        watcher
+            .set_poll_interval(Duration::from_millis(20))
+            .unwrap();
+        watcher.watch_file(&first).unwrap();
+
+        let counter = Arc::new(Mutex::new(0));
+        let counter_clone = Arc::clone(&counter);
+        watcher
+            .on_config_change(move || *counter_clone.lock().unwrap() += 1)
+            .unwrap();
+        watcher.start_watching().unwrap();
+
+        watcher.watch_file(&added).unwrap();
+        assert!(watcher.is_watching());
+        fs::write(&added, "{}").unwrap();
+        assert!(wait_for_count(&counter, 1));
+
+        // An unwatched file is no longer polled
+        watcher.unwatch_file(&added).unwrap();
+        let before = *counter.lock().unwrap();
+        fs::remove_file(&added).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(*counter.lock().unwrap(), before);
+    }
+
+    #[test]
+    fn test_watch_status_reports_missing_and_failed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        let other_path = temp_dir.path().join("other.json");
+        fs::write(&config_path, "{}").unwrap();
+        fs::write(&other_path, "{}").unwrap();
+
+        let mut watcher = FileWatcher::new(&config_path).unwrap();
+        watcher.watch_file(&other_path).unwrap();
+        watcher.start_watching().unwrap();
+        assert_eq!(
+            watcher.watch_status(),
+            vec![
+                (config_path.clone(), WatchState::Active),
+                (other_path.clone(), WatchState::Active),
+            ]
+        );
+
+        fs::remove_file(&config_path).unwrap();
+        let error = notify::Error::generic("watch limit reached").add_path(other_path.clone());
+        record_event(&watcher.states, watcher.watched_files(), &Err(error));
+        let status = watcher.watch_status();
+        assert_eq!(status[0], (config_path.clone(), WatchState::Missing));
+        assert!(
+            matches!(&status[1].1, WatchState::Error(message) if message.contains("watch limit reached"))
+        );
+
+        // Restarting re-establishes every watch, clearing recorded errors
+        watcher.stop_watching();
+        watcher.start_watching().unwrap();
+        assert_eq!(
+            watcher.watch_status(),
+            vec![
+                (config_path.clone(), WatchState::Missing),
+                (other_path.clone(), WatchState::Active),
+            ]
+        );
+
+        // A watch that cannot be established is reported as an error
+        let nested_dir = temp_dir.path().join("nested");
+        let nested_path = nested_dir.join("nested.json");
+        fs::create_dir(&nested_dir).unwrap();
+        fs::write(&nested_path, "{}").unwrap();
+        watcher.watch_file(&nested_path).unwrap();
+        watcher.stop_watching();
+        fs::remove_dir_all(&nested_dir).unwrap();
+        watcher.start_watching().unwrap();
+        let status = watcher.watch_status();
+        assert_eq!(status[1].1, WatchState::Active);
+        assert!(matches!(status[2].1, WatchState::Error(_)));
+
+        // Starting fails when no watch can be established at all
+        watcher.stop_watching();
+        fs::remove_dir_all(temp_dir.path()).unwrap();
+        assert!(matches!(
+            watcher.start_watching(),
+            Err(ConfigError::FileWatch(_))
+        ));
+        assert!(!watcher.is_watching());
+    }
+
+    #[test]
+    fn test_watch_status_while_polling() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+
+        let mut watcher = FileWatcher::new_empty().unwrap();
+        watcher
+            .set_poll_interval(Duration::from_millis(20))
+            .unwrap();
+        watcher.watch_file(&config_path).unwrap();
+        watcher.start_watching().unwrap();
+        assert_eq!(
+            watcher.watch_status(),
+            vec![(config_path.clone(), WatchState::Missing)]
+        );
+
+        fs::write(&config_path, "{}").unwrap();
+        assert_eq!(
+            watcher.watch_status(),
+            vec![(config_path.clone(), WatchState::Active)]
+        );
+    }
+
+    #[test]
+    fn test_changes_detected_after_atomic_save() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, r#"{"version": 1}"#).unwrap();
+
+        let mut watcher = FileWatcher::new_empty().unwrap();
+        watcher.watch_file(&config_path).unwrap();
+        let counter = Arc::new(Mutex::new(0));
+        let counter_clone = Arc::clone(&counter);
+        watcher
+            .on_config_change(move || *counter_clone.lock().unwrap() += 1)
+            .unwrap();
+        watcher.start_watching().unwrap();
+
+        // Save twice the way editors do: write a temporary file and rename it over
+        for version in 2..=3 {
+            let count = *counter.lock().unwrap();
+            let temp_path = temp_dir.path().join("config.json.tmp");
+            fs::write(&temp_path, format!(r#"{{"version": {version}}}"#)).unwrap();
+            fs::rename(&temp_path, &config_path).unwrap();
+            assert!(wait_for_count(&counter, count + 1));
+        }
+        assert_eq!(
+            watcher.watch_status(),
+            vec![(config_path.clone(), WatchState::Active)]
+        );
+
+        // Files sharing a directory stay watched until the last one is removed
+        let other_path = temp_dir.path().join("other.json");
+        fs::write(&other_path, "{}").unwrap();
+        watcher.watch_file(&other_path).unwrap();
+        watcher.unwatch_file(&config_path).unwrap();
+        watcher.unwatch_file(&other_path).unwrap();
+        assert!(watcher.watched_files().is_empty());
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        let matches = |pattern: &str, name: &str| {
+            glob_matches(
+                &pattern.chars().collect::<Vec<_>>(),
+                &name.chars().collect::<Vec<_>>(),
+            )
+        };
+        assert!(matches("*.swp", "config.yaml.swp"));
+        assert!(matches("*~", "config.yaml~"));
+        assert!(matches(".#*", ".#config.yaml"));
+        assert!(matches("config.?ml", "config.xml"));
+        assert!(matches("*", ""));
+        assert!(!matches("*.swp", "config.yaml"));
+        assert!(!matches("config.?ml", "config.yaml"));
+    }
+
+    #[test]
+    fn test_only_watched_files_trigger_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(&config_path, "a: 1").unwrap();
+
+        let event = |name: &str| {
+            Ok(
+                Event::new(EventKind::Modify(notify::event::ModifyKind::Any))
+                    .add_path(temp_dir.path().join(name)),
+            )
+        };
+        let files = [config_path.clone()];
+        assert!(is_relevant(&event("config.yaml"), &files, &[]));
+        for sibling in [
+            "config.yaml.swp",
+            "config.yaml~",
+            ".config.yaml.tmp",
+            "other.yaml",
+        ] {
+            assert!(!is_relevant(&event(sibling), &files, &[]));
+        }
+        assert!(!is_relevant(
+            &event("config.yaml"),
+            &files,
+            &["*.yaml".to_string()]
+        ));
+
+        let mut watcher = FileWatcher::new_empty().unwrap();
+        watcher.watch_file(&config_path).unwrap();
+        let counter = Arc::new(Mutex::new(0));
+        let counter_clone = Arc::clone(&counter);
+        watcher
+            .on_config_change(move || *counter_clone.lock().unwrap() += 1)
+            .unwrap();
+        watcher.start_watching().unwrap();
+
+        // Editor noise next to the file goes unnoticed, the file itself does not
+        fs::write(temp_dir.path().join("config.yaml.swp"), "swap").unwrap();
+        fs::write(temp_dir.path().join("config.yaml~"), "backup").unwrap();
+        thread::sleep(Duration::from_millis(300));
+        assert_eq!(*counter.lock().unwrap(), 0);
+        fs::write(&config_path, "a: 2").unwrap();
+        assert!(wait_for_count(&counter, 1));
+    }
+
     #[test]
     fn test_callback_error_handling() {
         let temp_dir = TempDir::new().unwrap();
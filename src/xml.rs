@@ -0,0 +1,585 @@
+//! Reading and writing XML configuration documents.
+//!
+//! Only the parts of XML that configuration files use are supported: elements,
+//! attributes, text, CDATA sections, comments, processing instructions, the predefined
+//! entities and character references. A DOCTYPE is skipped, and entities it declares are
+//! not expanded.
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::parser::parse_ini_value;
+use crate::value::ConfigValue;
+use std::collections::HashMap;
+
+/// Prefix of keys holding attribute values.
+const ATTRIBUTE_PREFIX: &str = "@";
+
+/// Key holding the text of an element that also has attributes or child elements.
+const TEXT_KEY: &str = "#text";
+
+/// Name of the root element written by `serialize_document`.
+const ROOT_ELEMENT: &str = "config";
+
+/// Deepest nesting of elements accepted when parsing, the same limit `serde_json` applies.
+/// Deeper documents are rejected rather than risking a stack overflow.
+const MAX_DEPTH: usize = 128;
+
+/// Parses an XML document into the configuration held by its root element.
+///
+/// Child elements become keys, repeated sibling elements become arrays, attributes become
+/// keys prefixed with `@`, and text is converted to integers, floats and booleans where
+/// it parses as one. The name of the root element itself is not part of the result.
+pub(crate) fn parse_document(content: &str) -> ConfigResult<HashMap<String, ConfigValue>> {
+    let mut reader = Reader::new(content);
+    reader.skip_misc()?;
+    if !reader.starts_with("<") {
+        return Err(reader.error("expected a root element"));
+    }
+    let root = reader.parse_element(0)?;
+    reader.skip_misc()?;
+    if !reader.at_end() {
+        return Err(reader.error("unexpected content after the root element"));
+    }
+
+    match element_to_value(root) {
+        ConfigValue::Object(map) => Ok(map),
+        ConfigValue::String(text) if text.is_empty() => Ok(HashMap::new()),
+        _ => Err(ConfigError::parse_error(
+            "XML",
+            "the root element must contain elements or attributes, not only text",
+        )),
+    }
+}
+
+/// Serializes configuration data as an XML document with a `<config>` root element.
+///
+/// This is the inverse of `parse_document` as far as XML allows: an array with a single
+/// element reads back as a plain value, and numeric or boolean strings read back as
+/// numbers or booleans.
+pub(crate) fn serialize_document(data: &HashMap<String, ConfigValue>) -> ConfigResult<String> {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    write_element(
+        &mut output,
+        ROOT_ELEMENT,
+        &ConfigValue::Object(data.clone()),
+        0,
+    )?;
+    Ok(output)
+}
+
+/// An element as read from the document.
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+fn element_to_value(element: Element) -> ConfigValue {
+    if element.attributes.is_empty() && element.children.is_empty() {
+        return text_to_value(&element.text);
+    }
+
+    let mut map = HashMap::new();
+    for (name, value) in element.attributes {
+        map.insert(format!("{ATTRIBUTE_PREFIX}{name}"), text_to_value(&value));
+    }
+    for child in element.children {
+        let name = child.name.clone();
+        let value = element_to_value(child);
+        match map.remove(&name) {
+            None => {
+                map.insert(name, value);
+            }
+            Some(ConfigValue::Array(mut items)) => {
+                items.push(value);
+                map.insert(name, ConfigValue::Array(items));
+            }
+            Some(first) => {
+                map.insert(name, ConfigValue::Array(vec![first, value]));
+            }
+        }
+    }
+    let text = element.text.trim();
+    if !text.is_empty() {
+        map.insert(TEXT_KEY.to_string(), text_to_value(text));
+    }
+    ConfigValue::Object(map)
+}
+
+fn text_to_value(text: &str) -> ConfigValue {
+    let text = text.trim();
+    if text.is_empty() {
+        ConfigValue::String(String::new())
+    } else {
+        parse_ini_value(text)
+    }
+}
+
+fn write_element(
+    output: &mut String,
+    name: &str,
+    value: &ConfigValue,
+    depth: usize,
+) -> ConfigResult<()> {
+    if !is_valid_name(name) {
+        return Err(ConfigError::serialization(format!(
+            "'{name}' is not a valid XML element name"
+        )));
+    }
+    let indent = "  ".repeat(depth);
+
+    match value {
+        ConfigValue::Array(items) => {
+            for item in items {
+                write_element(output, name, item, depth)?;
+            }
+        }
+        ConfigValue::Null => output.push_str(&format!("{indent}<{name}/>\n")),
+        ConfigValue::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            output.push_str(&format!("{indent}<{name}"));
+            let mut text = None;
+            let mut children = Vec::new();
+            for (key, value) in entries {
+                if key == TEXT_KEY {
+                    text = Some(value);
+                } else if let Some(attribute) = key.strip_prefix(ATTRIBUTE_PREFIX) {
+                    if !is_valid_name(attribute) {
+                        return Err(ConfigError::serialization(format!(
+                            "'{attribute}' is not a valid XML attribute name"
+                        )));
+                    }
+                    let value = scalar_text(value).ok_or_else(|| {
+                        ConfigError::serialization(format!(
+                            "attribute '{attribute}' must have a scalar value"
+                        ))
+                    })?;
+                    output.push_str(&format!(" {attribute}=\"{}\"", escape(&value, true)));
+                } else {
+                    children.push((key, value));
+                }
+            }
+
+            let text = match text {
+                Some(value) => Some(scalar_text(value).ok_or_else(|| {
+                    ConfigError::serialization(format!(
+                        "the text of element '{name}' must be a scalar value"
+                    ))
+                })?),
+                None => None,
+            };
+            match (text, children.is_empty()) {
+                (None, true) => output.push_str("/>\n"),
+                (Some(text), true) => {
+                    output.push_str(&format!(">{}</{name}>\n", escape(&text, false)));
+                }
+                (text, false) => {
+                    output.push_str(">\n");
+                    if let Some(text) = text {
+                        output.push_str(&format!("{indent}  {}\n", escape(&text, false)));
+                    }
+                    for (key, value) in children {
+                        write_element(output, key, value, depth + 1)?;
+                    }
+                    output.push_str(&format!("{indent}</{name}>\n"));
+                }
+            }
+        }
+        scalar => {
+            let text = scalar_text(scalar).unwrap_or_default();
+            output.push_str(&format!(
+                "{indent}<{name}>{}</{name}>\n",
+                escape(&text, false)
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn scalar_text(value: &ConfigValue) -> Option<String> {
+    match value {
+        ConfigValue::String(s) => Some(s.clone()),
+        ConfigValue::Integer(i) => Some(i.to_string()),
+        ConfigValue::Float(f) => Some(f.to_string()),
+        ConfigValue::Boolean(b) => Some(b.to_string()),
+        ConfigValue::Null => Some(String::new()),
+        ConfigValue::Array(_) | ConfigValue::Object(_) => None,
+    }
+}
+
+fn escape(text: &str, in_attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if in_attribute => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == ':'
+}
+
+fn is_name_char(c: char) -> bool {
+    is_name_start(c) || c.is_numeric() || c == '-' || c == '.'
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(is_name_start) && chars.all(is_name_char)
+}
+
+/// Cursor over the document text.
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(input: &'a str) -> Self {
+        // A byte order mark is not part of the document
+        let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+        Self { input, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.rest().starts_with(prefix)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, message: &str) -> ConfigError {
+        let line = self.input[..self.pos].matches('\n').count() + 1;
+        ConfigError::parse_error("XML", format!("{message} at line {line}"))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Advances past the next occurrence of `terminator`.
+    fn skip_past(&mut self, terminator: &str, what: &str) -> ConfigResult<&'a str> {
+        match self.rest().find(terminator) {
+            Some(end) => {
+                let skipped = &self.rest()[..end];
+                self.pos += end + terminator.len();
+                Ok(skipped)
+            }
+            None => Err(self.error(&format!("unterminated {what}"))),
+        }
+    }
+
+    /// Skips whitespace, comments, processing instructions and a DOCTYPE.
+    fn skip_misc(&mut self) -> ConfigResult<()> {
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("<?") {
+                self.skip_past("?>", "processing instruction")?;
+            } else if self.starts_with("<!--") {
+                self.skip_past("-->", "comment")?;
+            } else if self.starts_with("<!DOCTYPE") {
+                self.skip_doctype()?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn skip_doctype(&mut self) -> ConfigResult<()> {
+        let mut depth = 0usize;
+        for (offset, c) in self.rest().char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                '>' if depth == 0 => {
+                    self.pos += offset + 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        Err(self.error("unterminated DOCTYPE"))
+    }
+
+    fn parse_name(&mut self) -> ConfigResult<String> {
+        let rest = self.rest();
+        if !rest.chars().next().is_some_and(is_name_start) {
+            return Err(self.error("expected a name"));
+        }
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| !is_name_char(c))
+            .map_or(rest.len(), |(i, _)| i);
+        self.pos += end;
+        Ok(rest[..end].to_string())
+    }
+
+    fn expect(&mut self, expected: char) -> ConfigResult<()> {
+        if self.peek() == Some(expected) {
+            self.pos += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{expected}'")))
+        }
+    }
+
+    /// Parses an element starting at its `<`, nested `depth` elements below the root.
+    fn parse_element(&mut self, depth: usize) -> ConfigResult<Element> {
+        if depth >= MAX_DEPTH {
+            return Err(self.error(&format!("elements nested deeper than {MAX_DEPTH} levels")));
+        }
+        self.expect('<')?;
+        let mut element = Element {
+            name: self.parse_name()?,
+            attributes: Vec::new(),
+            children: Vec::new(),
+            text: String::new(),
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.starts_with(">") {
+                self.pos += 1;
+                break;
+            }
+            let name = self.parse_name()?;
+            self.skip_whitespace();
+            self.expect('=')?;
+            self.skip_whitespace();
+            let quote = match self.peek() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let raw = self.skip_past(&quote.to_string(), "attribute value")?;
+            if element
+                .attributes
+                .iter()
+                .any(|(existing, _)| *existing == name)
+            {
+                return Err(self.error(&format!("duplicate attribute '{name}'")));
+            }
+            let value = self.unescape(raw)?;
+            element.attributes.push((name, value));
+        }
+
+        loop {
+            if self.at_end() {
+                return Err(self.error(&format!("unclosed element <{}>", element.name)));
+            }
+            if self.starts_with("</") {
+                self.pos += 2;
+                let name = self.parse_name()?;
+                if name != element.name {
+                    return Err(
+                        self.error(&format!("expected </{}> but found </{name}>", element.name))
+                    );
+                }
+                self.skip_whitespace();
+                self.expect('>')?;
+                return Ok(element);
+            } else if self.starts_with("<!--") {
+                self.skip_past("-->", "comment")?;
+            } else if self.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let data = self.skip_past("]]>", "CDATA section")?;
+                element.text.push_str(data);
+            } else if self.starts_with("<?") {
+                self.skip_past("?>", "processing instruction")?;
+            } else if self.starts_with("<") {
+                element.children.push(self.parse_element(depth + 1)?);
+            } else {
+                let end = self.rest().find('<').unwrap_or(self.rest().len());
+                let raw = &self.rest()[..end];
+                let text = self.unescape(raw)?;
+                element.text.push_str(&text);
+                self.pos += end;
+            }
+        }
+    }
+
+    /// Replaces entity and character references in `raw`.
+    fn unescape(&self, raw: &str) -> ConfigResult<String> {
+        let mut output = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(start) = rest.find('&') {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let end = after
+                .find(';')
+                .ok_or_else(|| self.error("unterminated entity reference"))?;
+            let entity = &after[..end];
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = if let Some(hex) = entity.strip_prefix("#x") {
+                        u32::from_str_radix(hex, 16).ok()
+                    } else if let Some(decimal) = entity.strip_prefix('#') {
+                        decimal.parse().ok()
+                    } else {
+                        None
+                    };
+                    code.and_then(char::from_u32).ok_or_else(|| {
+                        self.error(&format!("unknown entity reference '&{entity};'"))
+                    })?
+                }
+            };
+            output.push(c);
+            rest = &after[end + 1..];
+        }
+        output.push_str(rest);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_document() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- Legacy service configuration -->
+<!DOCTYPE config [ <!ELEMENT config ANY> ]>
+<config version="2">
+    <database host="db.internal" port="5432">
+        <ssl>true</ssl>
+        <timeout>2.5</timeout>
+    </database>
+    <server>api-1</server>
+    <server>api-2</server>
+    <greeting><![CDATA[Hello <world> & friends]]></greeting>
+    <motto>Fish &amp; Chips &#169; &#x263A;</motto>
+    <empty/>
+    <label lang="en">Welcome</label>
+</config>"#;
+
+        let parsed = parse_document(content).unwrap();
+        assert_eq!(parsed["@version"], ConfigValue::Integer(2));
+        assert_eq!(
+            parsed["database"],
+            crate::config_value!({
+                "@host": "db.internal",
+                "@port": 5432,
+                "ssl": true,
+                "timeout": 2.5
+            })
+        );
+        assert_eq!(
+            parsed["server"],
+            ConfigValue::Array(vec![ConfigValue::from("api-1"), ConfigValue::from("api-2")])
+        );
+        assert_eq!(
+            parsed["greeting"],
+            ConfigValue::from("Hello <world> & friends")
+        );
+        assert_eq!(parsed["motto"], ConfigValue::from("Fish & Chips © ☺"));
+        assert_eq!(parsed["empty"], ConfigValue::from(""));
+        assert_eq!(
+            parsed["label"],
+            crate::config_value!({ "@lang": "en", "#text": "Welcome" })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", "expected a root element"),
+            ("<config><a>1</b></config>", "expected </a> but found </b>"),
+            ("<config><a>1</a>", "unclosed element <config>"),
+            ("<config a=\"1\" a=\"2\"/>", "duplicate attribute 'a'"),
+            ("<config>&bogus;</config>", "unknown entity reference"),
+            (
+                "<config/><other/>",
+                "unexpected content after the root element",
+            ),
+            ("<config>just text</config>", "must contain elements"),
+        ];
+        for (content, expected) in cases {
+            let error = parse_document(content).unwrap_err();
+            assert!(error.to_string().contains(expected), "{content:?}: {error}");
+        }
+
+        let error = parse_document("<config>\n  <a>\n</config>").unwrap_err();
+        assert!(error.to_string().contains("line 3"), "{error}");
+    }
+
+    #[test]
+    fn test_parse_depth_limit() {
+        let nested = |depth: usize| {
+            format!(
+                "<config>{}1{}</config>",
+                "<a>".repeat(depth - 1),
+                "</a>".repeat(depth - 1)
+            )
+        };
+        assert!(parse_document(&nested(MAX_DEPTH)).is_ok());
+
+        // Far deeper documents fail with a parse error instead of overflowing the stack
+        for depth in [MAX_DEPTH + 1, 1_000_000] {
+            let error = parse_document(&nested(depth)).unwrap_err();
+            assert!(matches!(error, ConfigError::Parse { .. }), "{error}");
+            assert!(error.to_string().contains("nested deeper"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_serialize_round_trip() {
+        let data = match crate::config_value!({
+            "@version": 2,
+            "database": { "@host": "db & co", "port": 5432, "ssl": false },
+            "servers": ["api-1", "api-2"],
+            "note": "a < b",
+            "label": { "@lang": "en", "#text": "Welcome" },
+            "nothing": null
+        }) {
+            ConfigValue::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        let xml = serialize_document(&data).unwrap();
+        assert!(xml.contains(r#"<config version="2">"#));
+        assert!(xml.contains(r#"<database host="db &amp; co">"#));
+        assert!(xml.contains("<note>a &lt; b</note>"));
+        assert!(xml.contains(r#"<label lang="en">Welcome</label>"#));
+
+        let mut parsed = parse_document(&xml).unwrap();
+        assert_eq!(parsed.remove("nothing"), Some(ConfigValue::from("")));
+        let mut expected = data;
+        expected.remove("nothing");
+        assert_eq!(parsed, expected);
+
+        let invalid: HashMap<String, ConfigValue> =
+            [("1st".to_string(), ConfigValue::from(1i64))].into();
+        assert!(matches!(
+            serialize_document(&invalid),
+            Err(ConfigError::Serialization(_))
+        ));
+    }
+}
@@ -255,16 +255,19 @@ fn test_file_watching_with_invalid_content() {
 
     // Check callback count after invalid content write - should be 0
     let invalid_callback_count = *callback_count.lock().unwrap();
-    
+
     // Configuration should remain unchanged due to invalid content
     assert_eq!(
         spice_instance.get_string("app.name").unwrap(),
         Some("valid-app".to_string())
     );
-    
+
     // After accessing config with invalid file, callback should still not have been called
-    assert_eq!(*callback_count.lock().unwrap(), invalid_callback_count, 
-               "Callback should not be triggered for invalid configuration");
+    assert_eq!(
+        *callback_count.lock().unwrap(),
+        invalid_callback_count,
+        "Callback should not be triggered for invalid configuration"
+    );
 
     // Write valid content again
     let new_valid_content = r#"{
@@ -281,8 +284,11 @@ fn test_file_watching_with_invalid_content() {
         spice_instance.get_string("app.name").unwrap(),
         Some("recovered-app".to_string())
     );
-    
+
     // Now callback should have been called exactly once (for the valid recovery)
-    assert_eq!(*callback_count.lock().unwrap(), 1, 
-               "Callback should be triggered exactly once for valid configuration recovery");
+    assert_eq!(
+        *callback_count.lock().unwrap(),
+        1,
+        "Callback should be triggered exactly once for valid configuration recovery"
+    );
 }
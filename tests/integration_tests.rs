@@ -549,6 +549,7 @@ fn test_configuration_merging_and_overrides() {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_type_coercion_across_sources() {
     // Clean up any existing environment variables that might interfere
     env::remove_var("TEST_NUMBERS_ENV_INT");
@@ -94,12 +94,12 @@ fn test_complete_struct_deserialization() {
 
     assert_eq!(app_config.name, "test-app");
     assert_eq!(app_config.version, "1.0.0");
-    assert_eq!(app_config.debug, true);
+    assert!(app_config.debug);
 
     // Test database config
     assert_eq!(app_config.database.host, "localhost");
     assert_eq!(app_config.database.port, 5432);
-    assert_eq!(app_config.database.ssl, true);
+    assert!(app_config.database.ssl);
     assert_eq!(app_config.database.timeout, 30); // default value
 
     // Test credentials
@@ -111,9 +111,9 @@ fn test_complete_struct_deserialization() {
     assert_eq!(app_config.servers.len(), 2);
     assert_eq!(app_config.servers[0].name, "web1");
     assert_eq!(app_config.servers[0].port, 8080);
-    assert_eq!(app_config.servers[0].enabled, true);
+    assert!(app_config.servers[0].enabled);
     assert_eq!(app_config.servers[1].name, "web2");
-    assert_eq!(app_config.servers[1].enabled, false);
+    assert!(!app_config.servers[1].enabled);
 
     // Test features map
     assert_eq!(app_config.features.get("auth"), Some(&true));
@@ -147,12 +147,12 @@ fn test_partial_struct_deserialization() {
 
     assert_eq!(app_config.name, "partial-app");
     assert_eq!(app_config.version, "2.0.0");
-    assert_eq!(app_config.debug, false); // default value
+    assert!(!app_config.debug); // default value
 
     // Test database with defaults
     assert_eq!(app_config.database.host, "db-server");
     assert_eq!(app_config.database.port, 3306);
-    assert_eq!(app_config.database.ssl, false); // default value
+    assert!(!app_config.database.ssl); // default value
     assert_eq!(app_config.database.timeout, 30); // default function
     assert!(app_config.database.credentials.is_none()); // optional field
 
@@ -223,24 +223,24 @@ fn test_struct_deserialization_with_multiple_sources() {
         .set("features", ConfigValue::Object(features))
         .unwrap();
 
-    // Test deserialization with merged sources  
+    // Test deserialization with merged sources
     let app_config: AppConfig = spice_instance.unmarshal().unwrap();
 
     assert_eq!(app_config.name, "multi-source-app"); // from file
     assert_eq!(app_config.version, "1.0.0"); // from file
-    assert_eq!(app_config.debug, true); // explicit override
+    assert!(app_config.debug); // explicit override
 
     // Database config from multiple sources
     assert_eq!(app_config.database.host, "prod-db"); // explicit override works correctly
     assert_eq!(app_config.database.port, 5432); // from file
-    assert_eq!(app_config.database.ssl, true); // from SpiceX default
+    assert!(app_config.database.ssl); // from SpiceX default
     assert_eq!(app_config.database.timeout, 60); // from SpiceX default (overrides serde default)
 
     // Servers from file
     assert_eq!(app_config.servers.len(), 1);
     assert_eq!(app_config.servers[0].name, "web1");
     assert_eq!(app_config.servers[0].port, 8080);
-    assert_eq!(app_config.servers[0].enabled, false); // default
+    assert!(!app_config.servers[0].enabled); // default
 
     // Features from explicit setting
     assert_eq!(app_config.features.get("auth"), Some(&true));
@@ -281,7 +281,7 @@ fn test_unmarshal_key_specific_section() {
 
     assert_eq!(db_config.host, "db-host");
     assert_eq!(db_config.port, 5432);
-    assert_eq!(db_config.ssl, false);
+    assert!(!db_config.ssl);
     assert_eq!(db_config.timeout, 30); // default value
 
     let credentials = db_config.credentials.unwrap();
@@ -336,12 +336,12 @@ fn test_struct_deserialization_with_environment_overrides() {
     let app_config: AppConfig = spice_instance.unmarshal().unwrap();
 
     assert_eq!(app_config.name, "env-test-app"); // from file
-    assert_eq!(app_config.debug, true); // from environment
+    assert!(app_config.debug); // from environment
 
     // Database config with environment overrides
     assert_eq!(app_config.database.host, "env-db-host"); // from environment
     assert_eq!(app_config.database.port, 5432); // from file
-    assert_eq!(app_config.database.ssl, true); // from environment
+    assert!(app_config.database.ssl); // from environment
 
     // Clean up environment variables
     env::remove_var("MYAPP_DATABASE_HOST");
@@ -418,10 +418,10 @@ fn test_complex_nested_array_deserialization() {
     assert_eq!(admin_group.members.len(), 2);
     assert_eq!(admin_group.members[0].id, 1);
     assert_eq!(admin_group.members[0].name, "Alice");
-    assert_eq!(admin_group.members[0].active, true);
+    assert!(admin_group.members[0].active);
     assert_eq!(admin_group.members[1].id, 2);
     assert_eq!(admin_group.members[1].name, "Bob");
-    assert_eq!(admin_group.members[1].active, false); // default value
+    assert!(!admin_group.members[1].active); // default value
 
     // Test second group
     let user_group = &config.groups[1];
@@ -429,5 +429,5 @@ fn test_complex_nested_array_deserialization() {
     assert_eq!(user_group.members.len(), 1);
     assert_eq!(user_group.members[0].id, 3);
     assert_eq!(user_group.members[0].name, "Charlie");
-    assert_eq!(user_group.members[0].active, false);
+    assert!(!user_group.members[0].active);
 }